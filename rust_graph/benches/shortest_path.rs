@@ -0,0 +1,67 @@
+//! Times Dijkstra's algorithm on randomly generated graphs with 10³ to 10⁵ nodes.
+//!
+//! Run with `cargo bench -p rust_graph`.
+
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rust_graph::DijkstraGraph;
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+const OUTGOING_LINES: usize = 4;
+const RUNS: u32 = 5;
+
+/// Every point is connected to the next one, so a path from the first to the last point always exists
+fn generate_graph(amount_of_points: usize, rng: &mut StdRng) -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+
+  for id in 0..amount_of_points
+  { graph.add_point(id, rng.gen_range(0. ..1090.), rng.gen_range(0. ..720.)); }
+
+  for from in 0..amount_of_points
+  {
+    if from + 1 < amount_of_points
+    { graph.add_line(from, from + 1, rng.gen_range(1..=255)); }
+
+    for _ in 1..OUTGOING_LINES
+    { graph.add_line(from, rng.gen_range(0..amount_of_points), rng.gen_range(1..=255)); }
+  }
+
+  graph.set_start(0);
+  graph.set_end(amount_of_points - 1);
+
+  return graph;
+}
+
+fn main()
+{
+  let mut rng = StdRng::seed_from_u64(0x5eed);
+
+  println!("{:>8} {:>8} {:>12} {:>12}", "points", "lines", "build", "search");
+
+  for amount_of_points in SIZES
+  {
+    let build_start = Instant::now();
+    let mut graph = generate_graph(amount_of_points, &mut rng);
+    let build_time = build_start.elapsed();
+
+    let mut search_time = Duration::ZERO;
+    for _ in 0..RUNS
+    {
+      let search_start = Instant::now();
+      graph.find_shortest_path();
+      search_time += search_start.elapsed();
+
+      assert!(graph.get_path().is_some(), "the generated graph always has a path");
+    }
+
+    println!(
+      "{:>8} {:>8} {:>12.2?} {:>12.2?}",
+      amount_of_points,
+      graph.lines().len(),
+      build_time,
+      search_time / RUNS,
+    );
+  }
+}
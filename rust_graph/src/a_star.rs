@@ -0,0 +1,62 @@
+//! The heuristic of A* and tools to judge it.
+//!
+//! A* estimates the distance from every node to the end with the straight line distance between them on the screen,
+//! multiplied by a scale. The positions of the nodes and the lengths of the lines are unrelated, so whether this
+//! estimate is any good depends on the scale. A heuristic is admissible if it never overestimates the distance to the
+//! end, only then A* is guaranteed to find the shortest path.
+
+use crate::{search::PathAlgorithm, DijkstraGraph, DijkstraNode};
+
+/// The estimated distance from `from` to `to`: their distance on the screen multiplied by `scale`, rounded down
+pub fn heuristic(from: &DijkstraNode, to: &DijkstraNode, scale: f32) -> u32
+{ return ((from.x - to.x).hypot(from.y - to.y) * scale).floor() as u32; }
+
+impl DijkstraGraph
+{
+  /// Returns the largest scale for which the heuristic never overestimates the length of a single positive line,
+  /// `None` if there are no positive lines between two nodes at different positions.
+  ///
+  /// Up to this scale the heuristic is consistent, which also makes it admissible for every end node. Lines of length
+  /// zero or less are left out, no positive scale could account for them.
+  pub fn consistent_scale(&self) -> Option<f32>
+  {
+    return self.lines().iter()
+      .map(|(_, from, distance, _, to)| (*distance as f32, (from.x - to.x).hypot(from.y - to.y)))
+      .filter(|(distance, screen_distance)| *distance > 0. && *screen_distance > 0.)
+      .map(|(distance, screen_distance)| distance / screen_distance)
+      .min_by(f32::total_cmp);
+  }
+
+  /// Returns the ids of all nodes whose estimated distance to the end is longer than their actual shortest path to
+  /// it, sorted.
+  ///
+  /// If this list isn't empty the heuristic is inadmissible and A* might not find the shortest path. Nodes that can't
+  /// reach the end are never listed.
+  pub fn inadmissible_points(&self, scale: f32) -> Vec<usize>
+  {
+    let Some(end_point) = self.end().and_then(|end| self.get(end)) else { return vec![]; };
+    let distances = self.distances_to(self.end().unwrap());
+
+    return self.points().iter().enumerate()
+      .filter_map(|(id, point_option)| Some((id, point_option.as_ref()?, distances[id]?)))
+      .filter(|(_, point, distance)| heuristic(point, end_point, scale) as i64 > *distance)
+      .map(|(id, _, _)| id)
+      .collect();
+  }
+
+  /// Searches the shortest path with the given algorithm on a copy of the graph and returns how many nodes it has
+  /// expanded; `None` if the start or the end isn't set.
+  pub fn expanded_points(&self, path_algorithm: PathAlgorithm) -> Option<usize>
+  {
+    if self.start().is_none() || self.end().is_none() { return None; }
+
+    let mut graph = self.clone();
+    graph.set_path_algorithm(path_algorithm);
+    return Some(graph.find_shortest_path());
+  }
+}
+
+// Tests
+#[path = "./tests/a_star_tests.rs"]
+#[cfg(test)]
+mod a_star_tests;
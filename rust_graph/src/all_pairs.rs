@@ -0,0 +1,135 @@
+//! The shortest paths between all pairs of nodes with the Floyd-Warshall algorithm.
+//!
+//! Its running time grows with the cube of the amount of nodes, so it's only meant for small graphs.
+
+use crate::DijkstraGraph;
+
+/// The length of the shortest path between two nodes
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Distance
+{
+  /// There is no path
+  Unreachable,
+  /// The shortest path has this length
+  Finite(i64),
+  /// A path can lead over a cycle of negative length, so there is no shortest path
+  NegativeInfinity,
+}
+
+/// The distances and next hops between all pairs of nodes, both indexed by `[from][to]` with the ids of the nodes
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DistanceMatrix
+{
+  /// The length of the shortest path between every pair of nodes
+  pub distances: Vec<Vec<Distance>>,
+  /// The node that follows `from` on the shortest path to `to`, `None` if there is no shortest path
+  pub next_hops: Vec<Vec<Option<usize>>>,
+}
+
+impl DistanceMatrix
+{
+  /// Returns the length of the shortest path from `from` to `to`
+  pub fn distance(&self, from: usize, to: usize) -> Distance
+  {
+    return self.distances.get(from)
+      .and_then(|row| row.get(to))
+      .copied()
+      .unwrap_or(Distance::Unreachable);
+  }
+
+  /// Follows the next hops from `from` to `to` and returns the ids of all nodes on the way, `None` if there is no
+  /// shortest path
+  pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>>
+  {
+    let Distance::Finite(_) = self.distance(from, to) else { return None; };
+
+    let mut path = vec![from];
+    while *path.last().unwrap() != to
+    {
+      // Without negative cycles a shortest path never visits a node twice
+      if path.len() > self.next_hops.len() { return None; }
+      path.push(self.next_hops[*path.last().unwrap()][to]?);
+    }
+
+    return Some(path);
+  }
+
+  /// Returns true if at least one node lies on a cycle of negative length
+  pub fn has_negative_cycle(&self) -> bool
+  { return (0..self.distances.len()).any(|id| self.distances[id][id] == Distance::NegativeInfinity); }
+}
+
+impl DijkstraGraph
+{
+  /// Finds the shortest paths between all pairs of nodes with the Floyd-Warshall algorithm. Negative lines are
+  /// allowed.
+  pub fn all_pairs_shortest_paths(&self) -> DistanceMatrix
+  {
+    let amount = self.points().len();
+    let mut distances: Vec<Vec<Option<i64>>> = vec![vec![None; amount]; amount];
+    let mut next_hops = vec![vec![None; amount]; amount];
+
+    for id in (0..amount).filter(|id| self.get(*id).is_some())
+    {
+      distances[id][id] = Some(0);
+      next_hops[id][id] = Some(id);
+    }
+
+    self.lines().iter()
+      .for_each(|(from, _, distance, to, _)|
+      {
+        if distances[*from][*to].is_none_or(|old_distance| old_distance > *distance as i64)
+        {
+          distances[*from][*to] = Some(*distance as i64);
+          next_hops[*from][*to] = Some(*to);
+        }
+      });
+
+    for over in 0..amount
+    {
+      for from in 0..amount
+      {
+        let Some(first_half) = distances[from][over] else { continue; };
+
+        for to in 0..amount
+        {
+          let Some(second_half) = distances[over][to] else { continue; };
+
+          if distances[from][to].is_none_or(|old_distance| old_distance > first_half + second_half)
+          {
+            distances[from][to] = Some(first_half + second_half);
+            next_hops[from][to] = next_hops[from][over];
+          }
+        }
+      }
+    }
+
+    let mut matrix = DistanceMatrix
+    {
+      distances: distances.iter()
+        .map(|row| row.iter().map(|distance| distance.map_or(Distance::Unreachable, Distance::Finite)).collect())
+        .collect(),
+      next_hops,
+    };
+
+    // Every path that can make a detour over a node on a negative cycle has no shortest length
+    for over in (0..amount).filter(|id| distances[*id][*id].is_some_and(|distance| distance < 0))
+    {
+      for from in (0..amount).filter(|from| distances[*from][over].is_some())
+      {
+        for to in (0..amount).filter(|to| distances[over][*to].is_some())
+        {
+          matrix.distances[from][to] = Distance::NegativeInfinity;
+          matrix.next_hops[from][to] = None;
+        }
+      }
+    }
+
+    return matrix;
+  }
+}
+
+// Tests
+#[path = "./tests/all_pairs_tests.rs"]
+#[cfg(test)]
+mod all_pairs_tests;
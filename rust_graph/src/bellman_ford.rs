@@ -0,0 +1,158 @@
+//! The Bellman-Ford shortest path algorithm, which also works with lines of negative length.
+//!
+//! If a cycle of negative length can be reached from the start, going around it once more makes every path over it
+//! shorter, so there is no shortest path at all. Bellman-Ford notices this and returns the cycle instead.
+
+use crate::DijkstraGraph;
+
+/// The result of relaxing every line until nothing changes anymore
+struct Relaxation
+{
+  distances: Vec<Option<i64>>,
+  parents: Vec<Option<usize>>,
+  negative_cycle: Option<Vec<usize>>,
+  /// The amount of times the lines of a node have been relaxed
+  expanded: usize,
+}
+
+/// Runs Bellman-Ford over `lines` given as `(from, to, length)`, with ids below `amount`
+fn relax_lines(amount: usize, lines: &[(usize, usize, i64)], source: usize) -> Relaxation
+{
+  let mut distances = vec![None; amount];
+  let mut parents = vec![None; amount];
+  distances[source] = Some(0);
+
+  let mut expanded = 0;
+  let mut relaxed_in_last_round = None;
+
+  // Without a negative cycle every shortest path has less than `amount` lines, so the last round changes nothing
+  for round in 0..amount
+  {
+    expanded += distances.iter().flatten().count();
+    let mut last_relaxed = None;
+
+    for (from, to, length) in lines.iter()
+    {
+      let Some(distance) = distances[*from] else { continue; };
+      if distances[*to].is_none_or(|old_distance| old_distance > distance + length)
+      {
+        distances[*to] = Some(distance + length);
+        parents[*to] = Some(*from);
+        last_relaxed = Some(*to);
+      }
+    }
+
+    if last_relaxed.is_none() { break; }
+    if round == amount - 1 { relaxed_in_last_round = last_relaxed; }
+  }
+
+  let negative_cycle = relaxed_in_last_round.map(|relaxed|
+  {
+    // Following the parents long enough is guaranteed to end up on the cycle
+    let mut on_cycle = relaxed;
+    for _ in 0..amount { on_cycle = parents[on_cycle].unwrap(); }
+
+    let mut cycle = vec![on_cycle];
+    let mut current = parents[on_cycle].unwrap();
+    while current != on_cycle
+    {
+      cycle.push(current);
+      current = parents[current].unwrap();
+    }
+
+    // The parents point backwards, the cycle starts at its lowest id
+    cycle.reverse();
+    let lowest = cycle.iter().enumerate().min_by_key(|(_, id)| **id).unwrap().0;
+    cycle.rotate_left(lowest);
+    return cycle;
+  });
+
+  return Relaxation { distances, parents, negative_cycle, expanded };
+}
+
+impl DijkstraGraph
+{
+  fn signed_lines(&self, reversed: bool) -> Vec<(usize, usize, i64)>
+  {
+    return self.lines().iter()
+      .map(|(from, _, distance, to, _)| if reversed { (*to, *from, *distance as i64) } else { (*from, *to, *distance as i64) })
+      .collect();
+  }
+
+  /// Returns the length of the shortest path from `start` to every node, indexed by id, even if some lines are
+  /// negative. Unreachable and missing nodes are `None`.
+  ///
+  /// If a cycle of negative length can be reached from `start` there are no shortest paths, the ids of the nodes on
+  /// the cycle are returned as the error instead. The cycle starts at its lowest id.
+  pub fn bellman_ford(&self, start: usize) -> Result<Vec<Option<i64>>, Vec<usize>>
+  {
+    if self.get(start).is_none() { return Ok(vec![None; self.points().len()]); }
+
+    let relaxation = relax_lines(self.points().len(), &self.signed_lines(false), start);
+    return match relaxation.negative_cycle
+    {
+      Some(cycle) => Err(cycle),
+      None => Ok(relaxation.distances),
+    };
+  }
+
+  /// Like [`DijkstraGraph::bellman_ford`], but returns the length of the shortest path from every node to `end`.
+  /// The error is a negative cycle from which `end` can be reached.
+  pub fn bellman_ford_to(&self, end: usize) -> Result<Vec<Option<i64>>, Vec<usize>>
+  {
+    if self.get(end).is_none() { return Ok(vec![None; self.points().len()]); }
+
+    let relaxation = relax_lines(self.points().len(), &self.signed_lines(true), end);
+    return match relaxation.negative_cycle
+    {
+      Some(mut cycle) =>
+      {
+        // The cycle has been found on the reversed lines
+        cycle[1..].reverse();
+        Err(cycle)
+      },
+      None => Ok(relaxation.distances),
+    };
+  }
+
+  /// Returns the cycle of negative length found by the last path search with Bellman-Ford; while it exists there is
+  /// no shortest path.
+  pub fn negative_cycle(&self) -> Option<&[usize]>
+  { return self.negative_cycle.as_deref(); }
+
+  /// Searches the shortest path with Bellman-Ford and writes the result into the nodes, like
+  /// [`crate::DijkstraSearch`] does. Returns the amount of times the lines of a node have been relaxed.
+  pub(crate) fn run_bellman_ford(&mut self) -> usize
+  {
+    let (Some(start), Some(end)) = (self.start(), self.end()) else { return 0; };
+    if self.get(start).is_none() { self.clear_start(); return 0; }
+    if self.get(end).is_none() { self.clear_end(); return 0; }
+
+    self.clear_path();
+    self.searched = true;
+
+    let relaxation = relax_lines(self.points().len(), &self.signed_lines(false), start);
+    if relaxation.negative_cycle.is_some()
+    {
+      self.negative_cycle = relaxation.negative_cycle;
+      return relaxation.expanded;
+    }
+
+    for (id, (distance, parent)) in relaxation.distances.iter().zip(relaxation.parents.iter()).enumerate()
+    {
+      let Some(point) = self.get_mut(id) else { continue; };
+      let Some(distance) = distance else { continue; };
+
+      point.distance = Some((*distance).clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+      point.parent = Some(parent.unwrap_or(start));
+      point.visited = true;
+    }
+
+    return relaxation.expanded;
+  }
+}
+
+// Tests
+#[path = "./tests/bellman_ford_tests.rs"]
+#[cfg(test)]
+mod bellman_ford_tests;
@@ -0,0 +1,155 @@
+//! Strongly connected components with Tarjan's algorithm, and the condensation of a graph.
+//!
+//! Two nodes are in the same strongly connected component if there is a path from each of them to the other. There
+//! can only be a path from a node to another one if the component of the second node is reachable from the component
+//! of the first one in the condensation, which is why a path search can fail even though the nodes look connected.
+
+use crate::DijkstraGraph;
+
+/// The strongly connected components of a graph
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Components
+{
+  /// The ids of the nodes of every component, sorted. The components are in topological order: lines between two
+  /// components always lead from the one with the lower index to the one with the higher index.
+  pub components: Vec<Vec<usize>>,
+  /// The index of the component of every node, indexed by id
+  pub component_of: Vec<Option<usize>>,
+}
+
+impl Components
+{
+  /// Returns the amount of components
+  pub fn len(&self) -> usize
+  { return self.components.len(); }
+
+  /// Returns true if the graph has no nodes
+  pub fn is_empty(&self) -> bool
+  { return self.components.is_empty(); }
+
+  /// Returns true if every node can reach every other node
+  pub fn is_strongly_connected(&self) -> bool
+  { return self.components.len() <= 1; }
+}
+
+impl DijkstraGraph
+{
+  /// Finds the strongly connected components with Tarjan's algorithm
+  pub fn strongly_connected_components(&self) -> Components
+  {
+    let amount = self.points().len();
+    // The order in which the nodes have been discovered, and the lowest one reachable from their subtree
+    let mut indices: Vec<Option<usize>> = vec![None; amount];
+    let mut low_links = vec![0; amount];
+    let mut on_stack = vec![false; amount];
+    let mut stack = vec![];
+    let mut next_index = 0;
+    let mut components = vec![];
+
+    for root in (0..amount).filter(|id| self.get(*id).is_some())
+    {
+      if indices[root].is_some() { continue; }
+
+      // Replaces the recursion, every entry is a node and the position of the next edge to look at
+      let mut call_stack = vec![(root, 0)];
+      indices[root] = Some(next_index);
+      low_links[root] = next_index;
+      next_index += 1;
+      stack.push(root);
+      on_stack[root] = true;
+
+      while let Some((id, position)) = call_stack.last_mut()
+      {
+        let id = *id;
+        let edges = self.get(id).unwrap().edges();
+
+        if let Some(edge) = edges.get(*position)
+        {
+          *position += 1;
+          let to = edge.destination();
+          if self.get(to).is_none() { continue; }
+
+          match indices[to]
+          {
+            None =>
+            {
+              indices[to] = Some(next_index);
+              low_links[to] = next_index;
+              next_index += 1;
+              stack.push(to);
+              on_stack[to] = true;
+              call_stack.push((to, 0));
+            },
+            Some(to_index) if on_stack[to] => low_links[id] = low_links[id].min(to_index),
+            Some(_) => (),
+          }
+          continue;
+        }
+
+        call_stack.pop();
+        if let Some((parent, _)) = call_stack.last()
+        { low_links[*parent] = low_links[*parent].min(low_links[id]); }
+
+        // `id` is the root of a component, which consists of everything above it on the stack
+        if Some(low_links[id]) == indices[id]
+        {
+          let mut component = vec![];
+          while let Some(member) = stack.pop()
+          {
+            on_stack[member] = false;
+            component.push(member);
+            if member == id { break; }
+          }
+          component.sort_unstable();
+          components.push(component);
+        }
+      }
+    }
+
+    // Tarjan's algorithm finds the components in reverse topological order
+    components.reverse();
+
+    let mut component_of = vec![None; amount];
+    components.iter().enumerate()
+      .for_each(|(index, component)| component.iter().for_each(|id| component_of[*id] = Some(index)));
+
+    return Components { components, component_of };
+  }
+
+  /// Collapses every component into a single node and returns the resulting graph, which has no cycles.
+  ///
+  /// The id of every node is the index of its component, it is placed at the average position of its members. Two
+  /// components are connected if any of their members are, the line gets the shortest length among them.
+  pub fn condensation(&self, components: &Components) -> DijkstraGraph
+  {
+    let mut condensation = DijkstraGraph::new();
+
+    components.components.iter().enumerate()
+      .for_each(|(index, component)|
+      {
+        let members = component.iter().filter_map(|id| self.get(*id));
+        let (x, y) = members.fold((0., 0.), |(x, y), point| (x + point.x, y + point.y));
+        condensation.add_point(index, x / component.len() as f32, y / component.len() as f32);
+      });
+
+    self.lines().iter()
+      .for_each(|(from, _, distance, to, _)|
+      {
+        let (Some(from), Some(to)) = (components.component_of[*from], components.component_of[*to]) else { return; };
+        if from == to { return; }
+
+        let existing_distance = condensation.get(from).unwrap().edges().iter()
+          .find(|edge| edge.destination() == to)
+          .map(|edge| edge.distance());
+        if existing_distance.is_none_or(|existing_distance| existing_distance > *distance)
+        { condensation.add_line(from, to, *distance); }
+      });
+
+    return condensation;
+  }
+}
+
+// Tests
+#[path = "./tests/components_tests.rs"]
+#[cfg(test)]
+mod components_tests;
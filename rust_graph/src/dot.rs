@@ -0,0 +1,492 @@
+//! Importing and exporting graphs in the [Graphviz DOT](https://graphviz.org/doc/info/lang.html) language.
+//!
+//! Exported files contain a `digraph`, or a `graph` if the graph is undirected, in which every node has a `pos`
+//! attribute and every edge has a `label` and a `weight` attribute holding its length. Positions are written in the
+//! coordinates of the visualiser, where `y` grows downwards.
+//!
+//! The importer understands the commonly used subset of the language: node and edge statements (including chains
+//! like `a -> b -> c`), attribute lists, comments and quoted ids. Subgraphs and ports are rejected. Edge lengths
+//! are read from the `weight` attribute, falling back to a numeric `label` and finally to 1.
+
+use std::{collections::{HashMap, HashSet}, f32::consts::TAU, fmt::{self, Display}, fs, path::Path};
+
+use crate::DijkstraGraph;
+
+/// Nodes without a `pos` attribute are arranged on a circle around this point
+pub const DOT_LAYOUT_CENTER: (f32, f32) = (545., 360.);
+
+/// Numeric node names above this value are not used as ids
+pub const MAX_DOT_ID: usize = 1_000_000;
+
+/// Everything that can go wrong while importing or exporting a DOT file
+#[derive(Debug)]
+pub enum DotError
+{
+  /// The file could not be read or written
+  Io(std::io::Error),
+  /// The file doesn't follow the DOT syntax or uses a feature that isn't supported
+  Syntax
+  {
+    /// The line on which the error occured, starting at 1
+    line: usize,
+    /// What went wrong
+    message: String,
+  },
+}
+
+impl Display for DotError
+{
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    return match self
+    {
+      DotError::Io(error) => write!(formatter, "{}", error),
+      DotError::Syntax { line, message } => write!(formatter, "Malformed DOT file on line {}: {}", line, message),
+    };
+  }
+}
+
+impl std::error::Error for DotError {}
+
+impl From<std::io::Error> for DotError
+{
+  fn from(error: std::io::Error) -> Self
+  { return DotError::Io(error); }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token
+{
+  Id(String),
+  /// `->`
+  Arrow,
+  /// `--`
+  UndirectedEdge,
+  OpenBrace,
+  CloseBrace,
+  OpenBracket,
+  CloseBracket,
+  Semicolon,
+  Comma,
+  Equals,
+  Colon,
+}
+
+fn syntax_error<T>(line: usize, message: impl Into<String>) -> Result<T, DotError>
+{ return Err(DotError::Syntax { line, message: message.into() }); }
+
+/// Splits the source into tokens, every token is paired with the line it is on
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, DotError>
+{
+  let characters: Vec<char> = source.chars().collect();
+  let mut tokens = vec![];
+  let mut line = 1;
+  let mut index = 0;
+  let mut line_start = true;
+
+  while index < characters.len()
+  {
+    let character = characters[index];
+
+    match character
+    {
+      '\n' => { line += 1; index += 1; line_start = true; continue; },
+      _ if character.is_whitespace() => { index += 1; continue; },
+      // Lines starting with # are preprocessor output and ignored
+      '#' if line_start =>
+      {
+        while index < characters.len() && characters[index] != '\n' { index += 1; }
+        continue;
+      },
+      '/' if characters.get(index + 1) == Some(&'/') =>
+      {
+        while index < characters.len() && characters[index] != '\n' { index += 1; }
+        continue;
+      },
+      '/' if characters.get(index + 1) == Some(&'*') =>
+      {
+        let start_line = line;
+        index += 2;
+        loop
+        {
+          match (characters.get(index), characters.get(index + 1))
+          {
+            (None, _) => return syntax_error(start_line, "unterminated comment"),
+            (Some('*'), Some('/')) => { index += 2; break; },
+            (Some('\n'), _) => { line += 1; index += 1; },
+            _ => index += 1,
+          }
+        }
+        continue;
+      },
+      _ => (),
+    }
+
+    line_start = false;
+
+    let token = match character
+    {
+      '{' => Token::OpenBrace,
+      '}' => Token::CloseBrace,
+      '[' => Token::OpenBracket,
+      ']' => Token::CloseBracket,
+      ';' => Token::Semicolon,
+      ',' => Token::Comma,
+      '=' => Token::Equals,
+      ':' => Token::Colon,
+      '-' if characters.get(index + 1) == Some(&'>') => { index += 1; Token::Arrow },
+      '-' if characters.get(index + 1) == Some(&'-') => { index += 1; Token::UndirectedEdge },
+      '"' =>
+      {
+        let start_line = line;
+        let mut id = String::new();
+        index += 1;
+        loop
+        {
+          match characters.get(index)
+          {
+            None => return syntax_error(start_line, "unterminated string"),
+            Some('"') => break,
+            Some('\\') if characters.get(index + 1) == Some(&'"') => { id.push('"'); index += 1; },
+            // An escaped backslash can't escape the character after it
+            Some('\\') if characters.get(index + 1) == Some(&'\\') => { id.push_str("\\\\"); index += 1; },
+            // A backslash followed by a newline continues the string on the next line
+            Some('\\') if characters.get(index + 1) == Some(&'\n') => { line += 1; index += 1; },
+            Some(character) =>
+            {
+              if *character == '\n' { line += 1; }
+              id.push(*character);
+            },
+          }
+          index += 1;
+        }
+        Token::Id(id)
+      },
+      '<' => return syntax_error(line, "HTML strings are not supported"),
+      _ if character.is_alphanumeric() || character == '_' || character == '.' || character == '-' =>
+      {
+        let start = index;
+        while index + 1 < characters.len()
+          && (characters[index + 1].is_alphanumeric() || characters[index + 1] == '_' || characters[index + 1] == '.')
+        { index += 1; }
+        Token::Id(characters[start..=index].iter().collect())
+      },
+      _ => return syntax_error(line, format!("unexpected character '{}'", character)),
+    };
+
+    tokens.push((token, line));
+    index += 1;
+  }
+
+  return Ok(tokens);
+}
+
+/// A node or edge statement with the attributes that are relevant to the graph
+struct Statement
+{
+  line: usize,
+  /// One name for a node statement, two or more for an edge (chain)
+  names: Vec<String>,
+  attributes: HashMap<String, String>,
+}
+
+struct Parser
+{
+  tokens: Vec<(Token, usize)>,
+  index: usize,
+}
+
+impl Parser
+{
+  fn peek(&self) -> Option<&Token>
+  { return self.tokens.get(self.index).map(|(token, _)| token); }
+
+  /// The line of the current token, or of the last one if all tokens have been consumed
+  fn line(&self) -> usize
+  {
+    return self.tokens.get(self.index)
+      .or(self.tokens.last())
+      .map(|(_, line)| *line)
+      .unwrap_or(1);
+  }
+
+  fn next(&mut self) -> Option<Token>
+  {
+    let token = self.peek().cloned();
+    self.index += 1;
+    return token;
+  }
+
+  fn expect(&mut self, expected: Token, description: &str) -> Result<(), DotError>
+  {
+    let line = self.line();
+    return match self.next()
+    {
+      Some(token) if token == expected => Ok(()),
+      _ => syntax_error(line, format!("expected {}", description)),
+    };
+  }
+
+  fn id(&mut self) -> Result<String, DotError>
+  {
+    let line = self.line();
+    return match self.next()
+    {
+      Some(Token::Id(id)) => Ok(id),
+      _ => syntax_error(line, "expected an id"),
+    };
+  }
+
+  fn is_keyword(token: Option<&Token>, keyword: &str) -> bool
+  { return matches!(token, Some(Token::Id(id)) if id.eq_ignore_ascii_case(keyword)); }
+
+  /// Parses the whole file into its node and edge statements, and whether it is an undirected `graph`
+  fn graph(&mut self) -> Result<(Vec<Statement>, bool), DotError>
+  {
+    if Parser::is_keyword(self.peek(), "strict") { self.index += 1; }
+
+    let undirected = Parser::is_keyword(self.peek(), "graph");
+    if !undirected && !Parser::is_keyword(self.peek(), "digraph")
+    { return syntax_error(self.line(), "expected 'digraph' or 'graph'"); }
+    self.index += 1;
+    let (edge_token, wrong_edge_token) = if undirected
+    { (Token::UndirectedEdge, Token::Arrow) }
+    else
+    { (Token::Arrow, Token::UndirectedEdge) };
+
+    // The name of the graph is optional
+    if let Some(Token::Id(_)) = self.peek() { self.index += 1; }
+
+    self.expect(Token::OpenBrace, "'{'")?;
+
+    let mut statements = vec![];
+
+    loop
+    {
+      let line = self.line();
+
+      match self.peek()
+      {
+        None => return syntax_error(line, "expected '}'"),
+        Some(Token::CloseBrace) => { self.index += 1; break; },
+        Some(Token::Semicolon) => { self.index += 1; continue; },
+        Some(Token::OpenBrace) => return syntax_error(line, "subgraphs are not supported"),
+        token if Parser::is_keyword(token, "subgraph") => return syntax_error(line, "subgraphs are not supported"),
+        // Default attributes for the graph, nodes or edges are ignored
+        token if Parser::is_keyword(token, "graph") || Parser::is_keyword(token, "node") || Parser::is_keyword(token, "edge") =>
+        {
+          self.index += 1;
+          self.attributes()?;
+          continue;
+        },
+        _ => (),
+      }
+
+      let mut names = vec![self.id()?];
+
+      match self.peek()
+      {
+        // A graph attribute like `rankdir = LR`, it is ignored
+        Some(Token::Equals) =>
+        {
+          self.index += 1;
+          self.id()?;
+          continue;
+        },
+        Some(Token::Colon) => return syntax_error(line, "ports are not supported"),
+        Some(token) if *token == wrong_edge_token =>
+          return syntax_error(line, if undirected { "'->' can't be used in a graph" } else { "'--' can't be used in a digraph" }),
+        _ => (),
+      }
+
+      while self.peek() == Some(&edge_token)
+      {
+        self.index += 1;
+        if self.peek() == Some(&Token::OpenBrace) || Parser::is_keyword(self.peek(), "subgraph")
+        { return syntax_error(self.line(), "subgraphs are not supported"); }
+        names.push(self.id()?);
+      }
+
+      let attributes = self.attributes()?;
+      statements.push(Statement { line, names, attributes });
+    }
+
+    if self.peek().is_some()
+    { return syntax_error(self.line(), "unexpected content after the end of the graph"); }
+
+    return Ok((statements, undirected));
+  }
+
+  /// Parses any amount of consecutive `[ key = value, … ]` lists
+  fn attributes(&mut self) -> Result<HashMap<String, String>, DotError>
+  {
+    let mut attributes = HashMap::new();
+
+    while self.peek() == Some(&Token::OpenBracket)
+    {
+      self.index += 1;
+
+      loop
+      {
+        match self.peek()
+        {
+          Some(Token::CloseBracket) => { self.index += 1; break; },
+          Some(Token::Comma) | Some(Token::Semicolon) => { self.index += 1; continue; },
+          _ => (),
+        }
+
+        let key = self.id()?;
+        self.expect(Token::Equals, "'=' after an attribute name")?;
+        let value = self.id()?;
+        attributes.insert(key, value);
+      }
+    }
+
+    return Ok(attributes);
+  }
+}
+
+/// Parses a `pos` attribute like `"12.5,40"` or `"12.5,40!"`
+fn parse_position(value: &str) -> Option<(f32, f32)>
+{
+  let (x, y) = value.trim_end_matches('!').split_once(',')?;
+  let x = x.trim().parse::<f32>().ok()?;
+  let y = y.trim().parse::<f32>().ok()?;
+  if !x.is_finite() || !y.is_finite() { return None; }
+  return Some((x, y));
+}
+
+fn parse_distance(statement: &Statement) -> Result<i32, DotError>
+{
+  if let Some(weight) = statement.attributes.get("weight")
+  {
+    return match weight.trim().parse::<i32>()
+    {
+      Ok(distance) => Ok(distance),
+      Err(_) => syntax_error(statement.line, format!("invalid edge weight '{}'", weight)),
+    };
+  }
+
+  let label_distance = statement.attributes.get("label")
+    .and_then(|label| label.trim().parse::<i32>().ok());
+
+  return Ok(label_distance.unwrap_or(1));
+}
+
+impl DijkstraGraph
+{
+  /// Exports the graph as a DOT `digraph`, or as a `graph` if it is undirected
+  pub fn to_dot(&self) -> String
+  {
+    let (mut dot, edge) = if self.is_undirected()
+    { (String::from("graph {\n"), "--") }
+    else
+    { (String::from("digraph {\n"), "->") };
+
+    self.points().iter().enumerate()
+      .filter_map(|(id, option)| option.as_ref().map(|point| (id, point)))
+      .for_each(|(id, point)| dot.push_str(&format!("  {} [pos=\"{},{}\"];\n", id, point.x, point.y)));
+
+    // Graphviz rejects negative weights, the label is enough to read them back in
+    self.lines().iter()
+      // The other direction of an undirected line is implied
+      .filter(|(from, _, _, to, _)| !self.is_undirected() || from <= to)
+      .for_each(|(from, _, distance, to, _)|
+      {
+        if *distance >= 0
+        { dot.push_str(&format!("  {} {} {} [label=\"{}\", weight={}];\n", from, edge, to, distance, distance)); }
+        else
+        { dot.push_str(&format!("  {} {} {} [label=\"{}\"];\n", from, edge, to, distance)); }
+      });
+
+    dot.push_str("}\n");
+
+    return dot;
+  }
+
+  /// Imports a graph from a DOT `digraph`, or from a `graph` as an undirected graph.
+  ///
+  /// If all node names are distinct numbers they are used as ids, otherwise the nodes are numbered in the order they
+  /// appear in. Nodes without a position are arranged on a circle around [`DOT_LAYOUT_CENTER`]. The returned graph has no
+  /// node limit.
+  pub fn from_dot(dot: &str) -> Result<DijkstraGraph, DotError>
+  {
+    let (statements, undirected) = Parser { tokens: tokenize(dot)?, index: 0 }.graph()?;
+
+    // Collecting all node names in the order they appear in
+    let mut names: Vec<&String> = vec![];
+    let mut known_names: HashSet<&String> = HashSet::new();
+    let mut positions: HashMap<&String, (f32, f32)> = HashMap::new();
+    for statement in statements.iter()
+    {
+      for name in statement.names.iter()
+      {
+        if known_names.insert(name) { names.push(name); }
+      }
+
+      if statement.names.len() != 1 { continue; }
+      let Some(position) = statement.attributes.get("pos") else { continue; };
+      let Some(position) = parse_position(position)
+      else { return syntax_error(statement.line, format!("invalid position '{}'", position)); };
+      positions.insert(&statement.names[0], position);
+    }
+
+    // Names like "1" and "01" are different nodes but the same number
+    let numeric_ids: Option<Vec<usize>> = names.iter()
+      .map(|name| name.parse::<usize>().ok().filter(|id| *id <= MAX_DOT_ID))
+      .collect::<Option<Vec<usize>>>()
+      .filter(|numeric_ids| numeric_ids.iter().collect::<HashSet<&usize>>().len() == numeric_ids.len());
+    let ids: HashMap<&String, usize> = match numeric_ids
+    {
+      Some(numeric_ids) => names.iter().copied().zip(numeric_ids).collect(),
+      None => names.iter().enumerate().map(|(id, name)| (*name, id)).collect(),
+    };
+
+    let mut graph = DijkstraGraph::new();
+    graph.set_undirected(undirected);
+
+    let unpositioned_amount = names.iter().filter(|name| !positions.contains_key(*name)).count();
+    // Keeps neighbouring nodes about 60 pixels apart
+    let circle_radius = (unpositioned_amount as f32 * 60. / TAU).max(100.);
+    let mut unpositioned_index = 0;
+    for name in names.iter()
+    {
+      let (x, y) = match positions.get(*name)
+      {
+        Some(position) => *position,
+        None =>
+        {
+          let angle = TAU * unpositioned_index as f32 / unpositioned_amount as f32;
+          unpositioned_index += 1;
+          (DOT_LAYOUT_CENTER.0 + circle_radius * angle.cos(), DOT_LAYOUT_CENTER.1 + circle_radius * angle.sin())
+        },
+      };
+      graph.add_point(ids[name], x, y);
+    }
+
+    for statement in statements.iter().filter(|statement| statement.names.len() > 1)
+    {
+      let distance = parse_distance(statement)?;
+      statement.names.iter().zip(statement.names.iter().skip(1))
+        .for_each(|(from, to)| graph.add_line(ids[from], ids[to], distance));
+    }
+
+    return Ok(graph);
+  }
+
+  /// Writes the graph to a DOT file, see [`DijkstraGraph::to_dot`]
+  pub fn save_dot(&self, path: impl AsRef<Path>) -> Result<(), DotError>
+  {
+    fs::write(path, self.to_dot())?;
+    return Ok(());
+  }
+
+  /// Reads a graph from a DOT file, see [`DijkstraGraph::from_dot`]
+  pub fn load_dot(path: impl AsRef<Path>) -> Result<DijkstraGraph, DotError>
+  { return DijkstraGraph::from_dot(&fs::read_to_string(path)?); }
+}
+
+// Tests
+#[path = "./tests/dot_tests.rs"]
+#[cfg(test)]
+mod dot_tests;
@@ -0,0 +1,183 @@
+//! Maximum flow with the Edmonds-Karp algorithm, and the minimum cut that limits it.
+//!
+//! The length of every line is used as its capacity, lines of negative length can't carry any flow. As long as there
+//! is a path from the source to the sink over lines with capacity left, the shortest of them (by the amount of lines)
+//! is used to send more flow. Such a path may also go backwards over a line to take back flow that has been sent
+//! over it earlier.
+
+use std::collections::VecDeque;
+
+use crate::DijkstraGraph;
+
+/// A line of the graph and the flow it carries
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FlowLine
+{
+  /// The id of the node the line starts at
+  pub from: usize,
+  /// The id of the node the line leads to
+  pub to: usize,
+  /// The most flow the line can carry, its length or 0 for negative lengths
+  pub capacity: i64,
+  /// The flow the line carries
+  pub flow: i64,
+}
+
+/// One step of Edmonds-Karp, a path from the source to the sink over which more flow has been sent
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AugmentingPath
+{
+  /// The ids of the nodes on the path, from the source to the sink
+  pub path: Vec<usize>,
+  /// The flow that has been sent along the path, the capacity left on its tightest line
+  pub amount: i64,
+  /// How the flow of every line on the path has changed as `(index in MaxFlow::lines, change)`. The change is
+  /// negative for lines the path goes over backwards.
+  pub changes: Vec<(usize, i64)>,
+}
+
+/// The result of a maximum flow computation
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct MaxFlow
+{
+  /// Every line of the graph, in the order of [`DijkstraGraph::lines`]
+  pub lines: Vec<FlowLine>,
+  /// The total flow from the source to the sink
+  pub value: i64,
+  /// Every step of the algorithm, in order
+  pub augmenting_paths: Vec<AugmentingPath>,
+  /// Whether every node can still be reached from the source over lines with capacity left, indexed by id. These
+  /// nodes form the source side of the minimum cut.
+  pub source_side: Vec<bool>,
+}
+
+impl MaxFlow
+{
+  /// Returns the lines from the source side to the other side of the minimum cut as `(from, to)`. They are all used
+  /// up and their capacities add up to the value of the flow.
+  pub fn min_cut(&self) -> Vec<(usize, usize)>
+  {
+    return self.lines.iter()
+      .filter(|line| self.source_side[line.from] && !self.source_side[line.to])
+      .map(|line| (line.from, line.to))
+      .collect();
+  }
+
+  /// Returns the flow of every line after the first `steps` augmenting paths, in the order of `lines`
+  pub fn flows_after(&self, steps: usize) -> Vec<i64>
+  {
+    let mut flows = vec![0; self.lines.len()];
+    self.augmenting_paths.iter().take(steps)
+      .flat_map(|augmenting_path| augmenting_path.changes.iter())
+      .for_each(|(line, change)| flows[*line] += change);
+    return flows;
+  }
+}
+
+/// The residual network, every line has a forward edge at index `2 * line` and a backward one right after it. The
+/// capacity of the backward edge is the flow of the line.
+struct Residual
+{
+  heads: Vec<usize>,
+  capacities: Vec<i64>,
+  /// The indices of the edges leaving every node, indexed by id
+  outgoing: Vec<Vec<usize>>,
+}
+
+impl Residual
+{
+  /// Returns the edge every node has been reached over with a breadth-first search over edges with capacity left
+  fn search(&self, source: usize) -> Vec<Option<usize>>
+  {
+    let mut reached_over = vec![None; self.outgoing.len()];
+    let mut reached = vec![false; self.outgoing.len()];
+    reached[source] = true;
+    let mut queue = VecDeque::from([source]);
+
+    while let Some(id) = queue.pop_front()
+    {
+      for edge in self.outgoing[id].iter().filter(|edge| self.capacities[**edge] > 0)
+      {
+        let head = self.heads[*edge];
+        if reached[head] { continue; }
+        reached[head] = true;
+        reached_over[head] = Some(*edge);
+        queue.push_back(head);
+      }
+    }
+
+    return reached_over;
+  }
+}
+
+impl DijkstraGraph
+{
+  /// Sends as much flow as possible from `source` to `sink` with the Edmonds-Karp algorithm. Returns `None` if
+  /// either of them doesn't exist or they are the same node.
+  pub fn max_flow(&self, source: usize, sink: usize) -> Option<MaxFlow>
+  {
+    if self.get(source).is_none() || self.get(sink).is_none() || source == sink { return None; }
+
+    let amount = self.points().len();
+    let mut lines: Vec<FlowLine> = self.lines().iter()
+      .map(|(from, _, distance, to, _)| FlowLine { from: *from, to: *to, capacity: (*distance).max(0) as i64, flow: 0 })
+      .collect();
+
+    let mut residual = Residual
+    {
+      heads: Vec::with_capacity(lines.len() * 2),
+      capacities: Vec::with_capacity(lines.len() * 2),
+      outgoing: vec![vec![]; amount],
+    };
+    for line in lines.iter()
+    {
+      residual.outgoing[line.from].push(residual.heads.len());
+      residual.heads.push(line.to);
+      residual.capacities.push(line.capacity);
+      residual.outgoing[line.to].push(residual.heads.len());
+      residual.heads.push(line.from);
+      residual.capacities.push(0);
+    }
+
+    let mut augmenting_paths = vec![];
+    loop
+    {
+      let reached_over = residual.search(source);
+      if reached_over[sink].is_none()
+      {
+        let source_side = (0..amount).map(|id| id == source || reached_over[id].is_some()).collect();
+        let value = augmenting_paths.iter().map(|augmenting_path: &AugmentingPath| augmenting_path.amount).sum();
+        lines.iter_mut().enumerate().for_each(|(index, line)| line.flow = residual.capacities[index * 2 + 1]);
+        return Some(MaxFlow { lines, value, augmenting_paths, source_side });
+      }
+
+      // Going backwards from the sink over the edges the search has used
+      let mut edges = vec![];
+      let mut id = sink;
+      while let Some(edge) = reached_over[id]
+      {
+        edges.push(edge);
+        id = residual.heads[edge ^ 1];
+      }
+      edges.reverse();
+
+      let amount = edges.iter().map(|edge| residual.capacities[*edge]).min().unwrap();
+      let mut path = vec![source];
+      let mut changes = vec![];
+      for edge in edges
+      {
+        residual.capacities[edge] -= amount;
+        residual.capacities[edge ^ 1] += amount;
+        path.push(residual.heads[edge]);
+        changes.push((edge / 2, if edge % 2 == 0 { amount } else { -amount }));
+      }
+
+      augmenting_paths.push(AugmentingPath { path, amount, changes });
+    }
+  }
+}
+
+// Tests
+#[path = "./tests/flow_tests.rs"]
+#[cfg(test)]
+mod flow_tests;
@@ -0,0 +1,152 @@
+//! Hit-testing helpers used to find out what lies under the mouse, the quadratic Bézier curves that lines are drawn
+//! as when they have a counterpart in the opposite direction, and the circles self-loops are drawn as.
+
+/// Returns true if the point lies inside of or on the edge of the circle
+pub fn is_point_in_circle(
+  point_x: f32, point_y: f32,
+  circle_x: f32, circle_y: f32,
+  circle_radius: f32
+) -> bool
+{
+  return (circle_x - point_x).powf(2_f32) + (circle_y - point_y).powf(2_f32) <= circle_radius.powf(2_f32);
+}
+
+/// Returns true if the point lies inside of or on the edge of the rectangle; `rectangle_x` and `rectangle_y` are
+/// the top left corner
+pub fn is_point_in_rectangle(
+  point_x: f32, point_y: f32,
+  rectangle_x: f32, rectangle_y: f32,
+  rectangle_width: f32,
+  rectangle_height: f32,
+) -> bool
+{
+  if point_x < rectangle_x
+    || point_y < rectangle_y
+    || point_x > (rectangle_x + rectangle_width)
+    || point_y > (rectangle_y + rectangle_height)
+  { return false; }
+
+  return true;
+}
+
+/// The start, control point and end of a quadratic Bézier curve
+pub type Curve = ((f32, f32), (f32, f32), (f32, f32));
+
+/// How far a curved line bends away from the straight one, relative to the distance between its points
+pub const LINE_CURVATURE: f32 = 0.15;
+
+/// Returns the control point of the quadratic Bézier curve from `start` to `end`. The curve bends to the right when
+/// looking from `start` to `end` (with `y` growing downwards), so a line and its counterpart in the opposite direction
+/// bend away from each other.
+pub fn curve_control_point(start: (f32, f32), end: (f32, f32)) -> (f32, f32)
+{
+  let (delta_x, delta_y) = (end.0 - start.0, end.1 - start.1);
+  return (
+    (start.0 + end.0) / 2. - delta_y * LINE_CURVATURE,
+    (start.1 + end.1) / 2. + delta_x * LINE_CURVATURE,
+  );
+}
+
+/// Returns the point on the quadratic Bézier curve at `t`, which goes from 0 at `start` to 1 at `end`
+pub fn quadratic_bezier(start: (f32, f32), control: (f32, f32), end: (f32, f32), t: f32) -> (f32, f32)
+{
+  let u = 1. - t;
+  return (
+    u * u * start.0 + 2. * u * t * control.0 + t * t * end.0,
+    u * u * start.1 + 2. * u * t * control.1 + t * t * end.1,
+  );
+}
+
+/// Returns the direction of the quadratic Bézier curve at `t`, its length is the speed of the curve there
+pub fn quadratic_bezier_tangent(start: (f32, f32), control: (f32, f32), end: (f32, f32), t: f32) -> (f32, f32)
+{
+  return (
+    2. * (1. - t) * (control.0 - start.0) + 2. * t * (end.0 - control.0),
+    2. * (1. - t) * (control.1 - start.1) + 2. * t * (end.1 - control.1),
+  );
+}
+
+/// Returns the `t` at which the quadratic Bézier curve enters the circle of the given radius around its end, or 0
+/// if it starts inside of it already
+pub fn quadratic_bezier_exit(start: (f32, f32), control: (f32, f32), end: (f32, f32), radius: f32) -> f32
+{
+  let is_inside = |t: f32|
+  {
+    let point = quadratic_bezier(start, control, end, t);
+    return is_point_in_circle(point.0, point.1, end.0, end.1, radius);
+  };
+  if is_inside(0.) { return 0.; }
+
+  // The distance to the end shrinks steadily for the gentle curves lines are drawn with
+  let (mut outside, mut inside) = (0_f32, 1_f32);
+  for _ in 0..20
+  {
+    let middle = (outside + inside) / 2.;
+    if is_inside(middle) { inside = middle; } else { outside = middle; }
+  }
+  return outside;
+}
+
+/// Returns the shortest distance between the point and the line segment from `start` to `end`
+pub fn distance_to_segment(point: (f32, f32), start: (f32, f32), end: (f32, f32)) -> f32
+{
+  let (delta_x, delta_y) = (end.0 - start.0, end.1 - start.1);
+  let length_squared = delta_x * delta_x + delta_y * delta_y;
+  let t = if length_squared == 0. { 0. }
+  else { (((point.0 - start.0) * delta_x + (point.1 - start.1) * delta_y) / length_squared).clamp(0., 1.) };
+
+  return (point.0 - (start.0 + t * delta_x)).hypot(point.1 - (start.1 + t * delta_y));
+}
+
+/// Returns the shortest distance between the point and the quadratic Bézier curve, which is approximated by 32
+/// straight segments
+pub fn distance_to_quadratic_bezier(point: (f32, f32), start: (f32, f32), control: (f32, f32), end: (f32, f32)) -> f32
+{
+  const SEGMENTS: usize = 32;
+
+  return (0..SEGMENTS)
+    .map(|segment|
+    {
+      let from = quadratic_bezier(start, control, end, segment as f32 / SEGMENTS as f32);
+      let to = quadratic_bezier(start, control, end, (segment + 1) as f32 / SEGMENTS as f32);
+      return distance_to_segment(point, from, to);
+    })
+    .fold(f32::INFINITY, f32::min);
+}
+
+/// How far the center of a self-loop lies above the center of its node, relative to the radius of the node
+pub const SELF_LOOP_DISTANCE: f32 = 1.6;
+
+/// The radius of a self-loop relative to the radius of its node
+pub const SELF_LOOP_RADIUS: f32 = 1.;
+
+/// Returns the center and radius of the circle a self-loop of a node at `x`/`y` with the given radius is drawn as.
+/// The part of the circle outside of the node is the loop.
+pub fn self_loop_circle(x: f32, y: f32, radius: f32) -> ((f32, f32), f32)
+{ return ((x, y - radius * SELF_LOOP_DISTANCE), radius * SELF_LOOP_RADIUS); }
+
+/// Returns the two points where a self-loop leaves and enters its node again. The loop runs clockwise, so it leaves
+/// on the left and comes back on the right.
+pub fn self_loop_ends(x: f32, y: f32, radius: f32) -> ((f32, f32), (f32, f32))
+{
+  let (_, loop_radius) = self_loop_circle(x, y, radius);
+  let distance = radius * SELF_LOOP_DISTANCE;
+
+  // The intersection of two circles, measured from the center of the node towards the center of the loop
+  let along = (radius * radius - loop_radius * loop_radius + distance * distance) / (2. * distance);
+  let across = (radius * radius - along * along).max(0.).sqrt();
+  return ((x - across, y - along), (x + across, y - along));
+}
+
+/// Returns true if the point lies within `tolerance` of the self-loop of a node at `x`/`y`, but not on the node
+pub fn is_point_on_self_loop(point_x: f32, point_y: f32, x: f32, y: f32, radius: f32, tolerance: f32) -> bool
+{
+  let ((center_x, center_y), loop_radius) = self_loop_circle(x, y, radius);
+  let distance = (point_x - center_x).hypot(point_y - center_y);
+  return (distance - loop_radius).abs() <= tolerance && !is_point_in_circle(point_x, point_y, x, y, radius);
+}
+
+// Tests
+#[path = "./tests/geometry_tests.rs"]
+#[cfg(test)]
+mod geometry_tests;
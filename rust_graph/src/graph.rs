@@ -0,0 +1,690 @@
+//! The graph model and the shortest path search.
+
+use crate::{geometry::{curve_control_point, distance_to_quadratic_bezier, is_point_in_circle, is_point_on_self_loop, Curve}, search::{DijkstraSearch, PathAlgorithm, TieBreak}};
+
+/// ### Dijkstra Graph
+///
+/// A directed graph with weighted edges in which the shortest path between a start and an end node can be searched.
+/// It can also be made undirected, see [`DijkstraGraph::set_undirected`].
+///
+/// Data is stored in an adjacency list as a growable vector.
+#[derive(Clone, Default)]
+pub struct DijkstraGraph
+{
+  /// The actual graph data is stored here.
+  ///
+  /// Nodes are identified by their index. Removing a node leaves a `None` in its place, this way the ids of all
+  /// the other nodes stay stable.
+  points: Vec<Option<DijkstraNode>>,
+
+  /// The maximum amount of nodes this graph accepts, `None` means that there is no limit.
+  max_points: Option<usize>,
+
+  start: Option<usize>,
+  end: Option<usize>,
+
+  /// How the path search decides between two equally short paths
+  tie_break: TieBreak,
+
+  /// Whether the path search uses Dijkstra's algorithm, A* or Bellman-Ford
+  path_algorithm: PathAlgorithm,
+
+  /// Whether every line also leads back with the same length
+  undirected: bool,
+
+  /// A cycle of negative length found by the last path search, there is no shortest path if it exists
+  pub(crate) negative_cycle: Option<Vec<usize>>,
+
+  /// Counts the changes made to the graph, see [`DijkstraGraph::revision`]
+  pub(crate) revision: u64,
+
+  /// Whether the nodes hold the result of a path search, otherwise there is nothing for `clear_path` to reset.
+  /// This keeps building large graphs linear in time.
+  pub(crate) searched: bool,
+}
+
+impl DijkstraGraph
+{
+  /// Creates an empty graph without a node limit
+  pub fn new() -> DijkstraGraph
+  { return DijkstraGraph { ..DijkstraGraph::default() }; }
+
+  /// Clears the graph of all nodes, the node limit, the settings of the path search and whether the graph is
+  /// undirected are kept
+  pub fn clear(&mut self)
+  {
+    *self = DijkstraGraph
+    {
+      max_points: self.max_points,
+      tie_break: self.tie_break,
+      path_algorithm: self.path_algorithm,
+      undirected: self.undirected,
+      revision: self.revision + 1,
+      ..DijkstraGraph::default()
+    };
+  }
+
+  /// Returns a number that changes whenever a node or line is added, removed or changed, or the start or end is
+  /// set. Moving nodes around doesn't change it.
+  ///
+  /// Results computed from the graph can be cached as long as the revision stays the same.
+  pub fn revision(&self) -> u64
+  { return self.revision; }
+
+  /// Invalidates everything that has been computed from the graph
+  pub(crate) fn changed(&mut self)
+  {
+    self.revision += 1;
+    self.clear_path();
+  }
+
+  /// Returns the maximum amount of nodes, `None` if there is no limit
+  pub fn max_points(&self) -> Option<usize>
+  { return self.max_points; }
+
+  /// Sets the maximum amount of nodes; `None` removes the limit.
+  ///
+  /// Lowering the limit below the current amount of nodes does not remove any of them, it only prevents new ones
+  /// from being added.
+  pub fn set_max_points(&mut self, max_points: Option<usize>)
+  { self.max_points = max_points; }
+
+  /// Returns how the path search decides between two equally short paths
+  pub fn tie_break(&self) -> TieBreak
+  { return self.tie_break; }
+
+  /// Sets how the path search decides between two equally short paths
+  pub fn set_tie_break(&mut self, tie_break: TieBreak)
+  {
+    self.tie_break = tie_break;
+    self.changed();
+  }
+
+  /// Returns the algorithm the path search uses
+  pub fn path_algorithm(&self) -> PathAlgorithm
+  { return self.path_algorithm; }
+
+  /// Sets the algorithm the path search uses
+  pub fn set_path_algorithm(&mut self, path_algorithm: PathAlgorithm)
+  {
+    self.path_algorithm = path_algorithm;
+    self.changed();
+  }
+
+  /// Returns true if every line also leads back with the same length
+  pub fn is_undirected(&self) -> bool
+  { return self.undirected; }
+
+  /// Makes the graph undirected or directed again.
+  ///
+  /// In an undirected graph every line is stored in both directions with one shared length, so all algorithms treat
+  /// it like two lines. Adding, changing or removing a line always affects both directions. Lines that only lead one
+  /// way get their counterpart when the graph becomes undirected; if both directions have different lengths, the
+  /// shorter one is used. Making the graph directed again keeps both directions as independent lines.
+  pub fn set_undirected(&mut self, undirected: bool)
+  {
+    if self.undirected == undirected { return; }
+    self.undirected = undirected;
+
+    if undirected
+    {
+      self.undirected_lines().into_iter()
+        .for_each(|(a, b, distance)| self.add_line(a, b, distance));
+    }
+
+    self.changed();
+  }
+
+  /// Returns true if at least one line has a negative length, Dijkstra's algorithm and A* can't be trusted then
+  pub fn has_negative_lines(&self) -> bool
+  {
+    return self.points.iter()
+      .flatten()
+      .any(|point| point.edges.iter().any(|edge| edge.distance < 0));
+  }
+
+  /// Returns true if no more nodes can be added to the graph
+  pub fn is_full(&self) -> bool
+  {
+    let Some(max_points) = self.max_points else { return false; };
+    return self.size() >= max_points;
+  }
+
+  /// Resets the result of the last shortest path search
+  pub fn clear_path(&mut self)
+  {
+    if !self.searched { return; }
+    self.searched = false;
+    self.negative_cycle = None;
+
+    for option in self.points.iter_mut()
+    {
+      let Some(point) = option.as_mut() else { continue; };
+      point.parent = None;
+      point.distance = None;
+      point.visited = false;
+    }
+  }
+
+  /// Returns the amount of nodes in the graph
+  pub fn size(&self) -> usize
+  {
+    let mut size = 0;
+    self.points.iter().for_each(|node| { if node.is_some() { size += 1; } });
+    return size;
+  }
+
+  /// Inserts a node with the given id, the store grows as needed; existing nodes are left untouched
+  pub fn add_point(&mut self, id: usize, x: f32, y: f32)
+  {
+    if self.get(id).is_some() || self.is_full() { return; }
+    if id >= self.points.len() { self.points.resize(id + 1, None); }
+    self.points[id] = Some(DijkstraNode::new(x, y));
+    self.changed();
+  }
+
+  /// Inserts a node at the first free id
+  pub fn append_point(&mut self, x: f32, y: f32)
+  {
+    let id = self.points.iter()
+      .position(|node_option| node_option.is_none())
+      .unwrap_or(self.points.len());
+
+    self.add_point(id, x, y);
+  }
+
+  /// Removes the node with the given id and all lines leading to it, its id becomes free to be reused
+  pub fn remove_point(&mut self, id: usize)
+  {
+    let Some(node_option) = self.points.get_mut(id) else { return; };
+    *node_option = None;
+    // Otherwise they would lead to the next node that gets this id
+    self.points.iter_mut()
+      .flatten()
+      .for_each(|point| point.edges.retain(|edge| edge.destination != id));
+    self.changed();
+  }
+
+  /// Puts a removed node back with the given id and outgoing edges, even if the graph is full
+  pub(crate) fn restore_point(&mut self, id: usize, x: f32, y: f32, edges: Vec<Edge>)
+  {
+    if id >= self.points.len() { self.points.resize(id + 1, None); }
+    self.points[id] = Some(DijkstraNode { edges, ..DijkstraNode::new(x, y) });
+    self.changed();
+  }
+
+  /// Replaces the nodes, lines, start and end and whether the graph is undirected with the ones of another graph, the
+  /// settings like the node limit are kept. The revision keeps counting up, so nothing computed from either graph is
+  /// mistaken for a result of the new one.
+  pub(crate) fn restore(&mut self, other: &DijkstraGraph)
+  {
+    self.points = other.points.clone();
+    self.start = other.start;
+    self.end = other.end;
+    self.undirected = other.undirected;
+    self.revision = self.revision.max(other.revision);
+    // The copied nodes may still hold the result of a path search
+    self.searched = true;
+    self.changed();
+  }
+
+  /// Adds a line; if it already exists, the length gets updated. In an undirected graph the line in the opposite
+  /// direction is added or updated as well.
+  pub fn add_line(&mut self, from: usize, to: usize, distance: i32)
+  {
+    if self.get(from).is_none() { return; }
+    self.set_edge(from, to, distance);
+    if self.undirected && from != to && self.get(to).is_some() { self.set_edge(to, from, distance); }
+
+    self.changed();
+  }
+
+  /// Adds or updates a single edge of an existing node
+  fn set_edge(&mut self, from: usize, to: usize, distance: i32)
+  {
+    let point = self.get_mut(from).unwrap();
+
+    // Avoids duplicate edges
+    match point.edges.iter_mut().find(|edge| edge.destination == to)
+    {
+      Some(edge) => edge.distance = distance,
+      None => point.edges.push(Edge { destination: to, distance }),
+    }
+  }
+
+  /// Removes the line going from `from` to `to`. The line in the opposite direction is kept, unless the graph is
+  /// undirected.
+  pub fn remove_line(&mut self, from: usize, to: usize)
+  {
+    let Some(from_point) = self.get_mut(from) else { return; };
+    from_point.edges.retain(|edge| edge.destination != to);
+
+    if self.undirected
+    {
+      if let Some(to_point) = self.get_mut(to)
+      { to_point.edges.retain(|edge| edge.destination != from); }
+    }
+
+    self.changed();
+  }
+
+  /// Returns the node with the given id, if there is one
+  pub fn get(&self, id: usize) -> Option<&DijkstraNode>
+  { return self.points.get(id)?.as_ref(); }
+
+  /// Returns the node with the given id mutably, if there is one
+  pub fn get_mut(&mut self, id: usize) -> Option<&mut DijkstraNode>
+  { return self.points.get_mut(id)?.as_mut(); }
+
+  /// Returns the id of the node the path search starts at
+  pub fn start(&self) -> Option<usize>
+  { return self.start; }
+
+  /// Sets the node the path search starts at
+  pub fn set_start(&mut self, start: usize)
+  {
+    self.start = Some(start);
+    self.changed();
+  }
+
+  /// Unsets the node the path search starts at
+  pub fn clear_start(&mut self)
+  {
+    self.start = None;
+    self.changed();
+  }
+
+  /// Returns the id of the node the path search ends at
+  pub fn end(&self) -> Option<usize>
+  { return self.end; }
+
+  /// Sets the node the path search ends at
+  pub fn set_end(&mut self, end: usize)
+  {
+    self.end = Some(end);
+    self.changed();
+  }
+
+  /// Unsets the node the path search ends at
+  pub fn clear_end(&mut self)
+  {
+    self.end = None;
+    self.changed();
+  }
+
+  /// Searches the shortest path from the start to the end node with the algorithm set by
+  /// [`DijkstraGraph::set_path_algorithm`].
+  ///
+  /// Nothing happens if either the start or the end is not set. The result can be retrieved with
+  /// [`DijkstraGraph::get_path`], or [`DijkstraGraph::negative_cycle`] if Bellman-Ford has found one. Returns the
+  /// amount of nodes the search has expanded.
+  pub fn find_shortest_path(&mut self) -> usize
+  {
+    if self.path_algorithm == PathAlgorithm::BellmanFord { return self.run_bellman_ford(); }
+
+    let Some(mut search) = DijkstraSearch::new(self) else { return 0; };
+    while search.step(self).is_some() {}
+    return search.expanded();
+  }
+
+  /// Returns the ids of the nodes on the shortest path, starting with the start node.
+  ///
+  /// Returns `None` if no path has been found.
+  pub fn get_path(&self) -> Option<Vec<usize>>
+  {
+    if self.start.is_none() || self.end.is_none() || (self.start.is_none() && self.end.is_none()) { return None; }
+
+    let mut current_node = self.end.unwrap();
+    let mut path = vec![current_node];
+
+    for _ in 0..self.points.len()
+    {
+      current_node = self.get(current_node)?.parent?;
+
+      path.push(current_node);
+
+      if current_node == self.start.unwrap() { break; }
+    }
+
+    path.reverse();
+
+    // Parents that go around in a circle never lead back to the start
+    if path[0] != self.start.unwrap() { return None; }
+
+    return Some(path);
+  }
+
+  /// Returns all nodes indexed by their id, free ids are `None`
+  pub fn points(&self) -> &[Option<DijkstraNode>]
+  { return &self.points; }
+
+  /// Returns the smallest rectangle around the centers of all nodes as `((left, top), (right, bottom))`, `None` for an
+  /// empty graph
+  pub fn bounding_box(&self) -> Option<((f32, f32), (f32, f32))>
+  {
+    return self.points.iter()
+      .flatten()
+      .map(|point| ((point.x, point.y), (point.x, point.y)))
+      .reduce(|((left, top), (right, bottom)), ((x, y), _)| ((left.min(x), top.min(y)), (right.max(x), bottom.max(y))));
+  }
+
+  /// Returns the ids of all nodes inside the rectangle spanned by two opposite corners, in any order
+  pub fn points_in_rectangle(&self, (x1, y1): (f32, f32), (x2, y2): (f32, f32)) -> Vec<usize>
+  {
+    let (left, right) = (x1.min(x2), x1.max(x2));
+    let (top, bottom) = (y1.min(y2), y1.max(y2));
+    return self.points.iter().enumerate()
+      .filter_map(|(id, option)| option.as_ref().map(|point| (id, point)))
+      .filter(|(_, point)| (left..=right).contains(&point.x) && (top..=bottom).contains(&point.y))
+      .map(|(id, _)| id)
+      .collect();
+  }
+
+  /// Returns all lines as `(from id, from node, length, to id, to node)`
+  pub fn lines(&self) -> Vec<(usize, &DijkstraNode, i32, usize, &DijkstraNode)>
+  {
+    let mut lines = vec![];
+
+    self.points.iter().enumerate()
+      .filter(|(_, option)| option.is_some())
+      .map(|(from_id, option)| (from_id, option.as_ref().unwrap()))
+      .for_each(|(from_id, from_point)|
+      {
+        // It would take 2× as many lines of code to implement this in a functional style
+        from_point.edges.iter()
+          .for_each(|edge|
+          {
+            let Some(to_point) = self.get(edge.destination) else { return; };
+
+            lines.push((from_id, from_point, edge.distance, edge.destination, to_point));
+          });
+      });
+
+    return lines;
+  }
+
+  /// Returns true if the line is drawn as a curve, because the line in the opposite direction exists as well. The
+  /// lines of an undirected graph are always straight.
+  pub fn is_curved(&self, from: usize, to: usize) -> bool
+  {
+    if self.undirected || from == to { return false; }
+    return self.get(to).is_some_and(|point| point.edges.iter().any(|edge| edge.destination == from));
+  }
+
+  /// Returns the start, control point and end of the quadratic Bézier curve the line is drawn as, from the center of
+  /// one node to the center of the other. Straight lines have their control point in the middle. Self-loops are drawn
+  /// as circles instead, see [`crate::geometry::self_loop_circle`], so they have no curve.
+  pub fn line_curve(&self, from: usize, to: usize) -> Option<Curve>
+  {
+    if from == to { return None; }
+    let (from_point, to_point) = (self.get(from)?, self.get(to)?);
+    let (start, end) = ((from_point.x, from_point.y), (to_point.x, to_point.y));
+
+    let control = if self.is_curved(from, to) { curve_control_point(start, end) }
+    else { ((start.0 + end.0) / 2., (start.1 + end.1) / 2.) };
+
+    return Some((start, control, end));
+  }
+
+  /// Returns the line as `(from, to)` that passes closest to the given position, if it is at most `tolerance` away.
+  /// Curved lines are tested along their curve, self-loops along their circle around a node of the given radius.
+  pub fn find_hovered_line(&self, mouse_x: f32, mouse_y: f32, radius: f32, tolerance: f32) -> Option<(usize, usize)>
+  {
+    let self_loop = self.lines().iter()
+      .find(|(from, point, _, to, _)| from == to && is_point_on_self_loop(mouse_x, mouse_y, point.x, point.y, radius, tolerance))
+      .map(|(from, _, _, to, _)| (*from, *to));
+    if self_loop.is_some() { return self_loop; }
+
+    return self.lines().iter()
+      .filter_map(|(from, _, _, to, _)|
+      {
+        let (start, control, end) = self.line_curve(*from, *to)?;
+        let distance = distance_to_quadratic_bezier((mouse_x, mouse_y), start, control, end);
+        return (distance <= tolerance).then_some((distance, (*from, *to)));
+      })
+      .min_by(|(a, _), (b, _)| a.total_cmp(b))
+      .map(|(_, line)| line);
+  }
+
+  /// Returns the id of the node that the given position lies on
+  pub fn find_hovered_point(&self, mouse_x: f32, mouse_y: f32, radius: f32) -> Option<usize>
+  {
+    let mut point_id = None;
+
+    self.points.iter().enumerate()
+      .filter(|(_, option)| option.is_some())
+      .map(|(index, option)| (index, option.as_ref().unwrap()))
+      .for_each(|(index, point)|
+      {
+        if is_point_in_circle(mouse_x, mouse_y, point.x, point.y, radius)
+        {
+          point_id = Some(index);
+        }
+      });
+
+    return point_id;
+  }
+
+  /// Prints all lines, the start, the end and the path to stdout (for debugging)
+  pub fn print_graph_data(&self)
+  {
+    println!("Graph data:");
+    self.lines().iter()
+      .for_each(|(from_id, _, distance, to_id, _)|
+      {
+        println!("  {}--{}-->{}", from_id, distance, to_id);
+      });
+
+    match self.start
+    {
+      Some(id) => println!("Start: {}", id),
+      None => println!("Start: None"),
+    }
+
+    match self.end
+    {
+      Some(id) => println!("End: {}", id),
+      None => println!("End: None"),
+    }
+
+    match self.get_path()
+    {
+      Some(path) => println!("Path: {:?}", path),
+      None => println!("Path: None")
+    }
+  }
+
+  /// Replaces the current graph with a small one
+  pub fn insert_small_graph(&mut self)
+  {
+    self.clear();
+
+    self.append_point(942., 355.);
+    self.append_point(720., 208.);
+    self.append_point(198., 342.);
+    self.append_point(463., 507.);
+    self.append_point(735., 513.);
+    self.append_point(458., 346.);
+    self.append_point(468., 202.);
+    self.append_point(721., 360.);
+
+    self.add_line(3, 4, 3);
+    self.add_line(2, 5, 5);
+    self.add_line(5, 7, 4);
+    self.add_line(6, 1, 5);
+    self.add_line(1, 0, 5);
+    self.add_line(5, 1, 7);
+    self.add_line(3, 7, 5);
+    self.add_line(7, 0, 4);
+    self.add_line(2, 6, 4);
+    self.add_line(2, 3, 7);
+    self.add_line(6, 7, 6);
+    self.add_line(5, 4, 8);
+    self.add_line(4, 0, 3);
+  }
+
+  /// Replaces the current graph with a medium-sized one
+  pub fn insert_medium_graph(&mut self)
+  {
+    self.clear();
+
+    self.append_point(959., 211.);
+    self.append_point(967., 394.);
+    self.append_point(946., 532.);
+    self.append_point(144., 377.);
+    self.append_point(775., 295.);
+    self.append_point(734., 523.);
+    self.append_point(559., 493.);
+    self.append_point(570., 361.);
+    self.append_point(569., 200.);
+    self.append_point(353., 206.);
+    self.append_point(355., 350.);
+    self.append_point(342., 488.);
+
+    self.add_line(10, 6, 4);
+    self.add_line(7, 1, 5);
+    self.add_line(3, 9, 4);
+    self.add_line(11, 6, 4);
+    self.add_line(3, 11, 6);
+    self.add_line(5, 2, 20);
+    self.add_line(7, 4, 3);
+    self.add_line(11, 7, 3);
+    self.add_line(8, 4, 3);
+    self.add_line(10, 7, 3);
+    self.add_line(3, 10, 5);
+    self.add_line(4, 0, 1);
+    self.add_line(8, 0, 5);
+    self.add_line(9, 8, 4);
+    self.add_line(6, 5, 7);
+    self.add_line(4, 1, 2);
+  }
+
+  /// Replaces the current graph with a large one
+  pub fn insert_large_graph(&mut self)
+  {
+    self.clear();
+
+    self.append_point(595., 640.);
+    self.append_point(864., 300.);
+    self.append_point(550., 369.);
+    self.append_point(280., 606.);
+    self.append_point(748., 127.);
+    self.append_point(177., 71.);
+    self.append_point(467., 84.);
+    self.append_point(260., 431.);
+    self.append_point(928., 642.);
+    self.append_point(466., 181.);
+    self.append_point(433., 27.);
+    self.append_point(667., 52.);
+    self.append_point(847., 75.);
+    self.append_point(734., 270.);
+    self.append_point(931., 233.);
+    self.append_point(904., 389.);
+    self.append_point(423., 467.);
+    self.append_point(445., 551.);
+    self.append_point(691., 559.);
+
+    self.add_line(11, 12, 1);
+    self.add_line(5, 7, 12);
+    self.add_line(13, 2, 1);
+    self.add_line(15, 8, 10);
+    self.add_line(14, 8, 14);
+    self.add_line(1, 18, 9);
+    self.add_line(17, 18, 3);
+    self.add_line(16, 17, 2);
+    self.add_line(7, 3, 1);
+    self.add_line(0, 8, 1);
+    self.add_line(6, 4, 1);
+    self.add_line(15, 2, 2);
+    self.add_line(2, 7, 1);
+    self.add_line(2, 16, 3);
+    self.add_line(14, 15, 1);
+    self.add_line(4, 13, 3);
+    self.add_line(9, 2, 8);
+    self.add_line(12, 1, 2);
+    self.add_line(11, 4, 2);
+    self.add_line(10, 11, 1);
+    self.add_line(5, 10, 2);
+    self.add_line(9, 4, 3);
+    self.add_line(4, 1, 1);
+    self.add_line(15, 16, 5);
+    self.add_line(5, 6, 1);
+    self.add_line(17, 0, 1);
+    self.add_line(5, 9, 2);
+    self.add_line(1, 2, 1);
+    self.add_line(18, 8, 4);
+    self.add_line(16, 3, 2);
+    self.add_line(12, 14, 1);
+    self.add_line(3, 0, 1);
+  }
+}
+
+/// A node of the graph with its position and outgoing edges
+#[derive(Clone, Debug)]
+pub struct DijkstraNode
+{
+  /// The horizontal position
+  pub x: f32,
+  /// The vertical position, it grows downwards
+  pub y: f32,
+  pub(crate) parent: Option<usize>,
+  pub(crate) distance: Option<i32>,
+  pub(crate) visited: bool,
+  pub(crate) edges: Vec<Edge>,
+}
+
+impl DijkstraNode
+{
+  fn new(x: f32, y: f32) -> Self
+  {
+    DijkstraNode
+    {
+      x,
+      y,
+      parent: None,
+      distance: None,
+      visited: false,
+      edges: vec![],
+    }
+  }
+
+  /// The node this one has been reached from during the last path search
+  pub fn parent(&self) -> Option<usize>
+  { return self.parent; }
+
+  /// The distance from the start node found during the last path search
+  pub fn distance(&self) -> Option<i32>
+  { return self.distance; }
+
+  /// Whether the last path search has visited this node
+  pub fn visited(&self) -> bool
+  { return self.visited; }
+
+  /// The outgoing edges of this node
+  pub fn edges(&self) -> &[Edge]
+  { return &self.edges; }
+}
+
+/// A directed, weighted edge pointing to another node
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Edge
+{
+  pub(crate) destination: usize,
+  pub(crate) distance: i32,
+}
+
+impl Edge
+{
+  /// The id of the node this edge points to
+  pub fn destination(&self) -> usize
+  { return self.destination; }
+
+  /// The length of this edge, it can be negative
+  pub fn distance(&self) -> i32
+  { return self.distance; }
+}
+
+// Tests
+#[path = "./tests/graph_tests.rs"]
+#[cfg(test)]
+mod graph_tests;
@@ -0,0 +1,425 @@
+//! Undo and redo for the edits of a graph.
+//!
+//! Edits that go through an [`EditHistory`] are recorded as commands that know exactly what they have changed, so
+//! undoing one restores the graph as it was before, down to the order of the lines. Edits that change the whole graph
+//! at once, like clearing it or opening a file, keep a copy of the graph from before and after instead.
+
+use std::collections::VecDeque;
+
+use crate::{DijkstraGraph, Edge};
+
+/// The most edits that can be undone, the oldest ones are forgotten first
+pub const MAX_EDITS: usize = 100;
+
+/// How a single edge has changed, both directions of an undirected line are changed separately
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LineChange
+{
+  /// The id of the node the edge starts at
+  pub from: usize,
+  /// The id of the node the edge leads to
+  pub to: usize,
+  /// The position among the edges of `from` and the length before the edit, `None` if the edge didn't exist
+  pub before: Option<(usize, i32)>,
+  /// The position among the edges of `from` and the length after the edit, `None` if the edge has been removed
+  pub after: Option<(usize, i32)>,
+}
+
+/// How a single node has been moved
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PointMove
+{
+  /// The id of the node
+  pub id: usize,
+  /// The position before the move
+  pub from: (f32, f32),
+  /// The position after the move
+  pub to: (f32, f32),
+}
+
+/// An edit of a graph that can be undone and redone
+#[derive(Clone)]
+pub enum Edit
+{
+  /// A node has been added
+  AddPoint
+  {
+    /// The id the node has been added with
+    id: usize,
+    /// The horizontal position of the node
+    x: f32,
+    /// The vertical position of the node
+    y: f32,
+  },
+  /// A node has been removed together with its lines
+  RemovePoint
+  {
+    /// The id of the node
+    id: usize,
+    /// The horizontal position of the node
+    x: f32,
+    /// The vertical position of the node
+    y: f32,
+    /// The outgoing edges of the node
+    edges: Vec<Edge>,
+    /// The lines that have lead to the node as `(from, position among the edges of from, length)`
+    incoming: Vec<(usize, usize, i32)>,
+  },
+  /// A line has been added, removed or given another length
+  SetLines(Vec<LineChange>),
+  /// Nodes have been moved together
+  MovePoints(Vec<PointMove>),
+  /// The node the path search starts at has been set or unset
+  SetStart
+  {
+    /// The start before the edit
+    before: Option<usize>,
+    /// The start after the edit
+    after: Option<usize>,
+  },
+  /// The node the path search ends at has been set or unset
+  SetEnd
+  {
+    /// The end before the edit
+    before: Option<usize>,
+    /// The end after the edit
+    after: Option<usize>,
+  },
+  /// Several edits that are undone and redone together
+  Batch(Vec<Edit>),
+  /// The whole graph has been changed at once, see [`EditHistory::replace`]
+  ReplaceGraph
+  {
+    /// A copy of the graph before the edit
+    before: Box<DijkstraGraph>,
+    /// A copy of the graph after the edit
+    after: Box<DijkstraGraph>,
+  },
+}
+
+impl Edit
+{
+  /// Describes the edit in a few words, like "add a point"
+  pub fn describe(&self) -> String
+  {
+    return String::from(match self
+    {
+      Edit::AddPoint { .. } => "add a point",
+      Edit::RemovePoint { .. } => "remove a point",
+      Edit::SetLines(changes) => match changes.first().map(|change| (change.before, change.after))
+      {
+        None => "change no lines",
+        Some((None, _)) => "add a line",
+        Some((_, None)) => "remove a line",
+        Some(_) => "change the length of a line",
+      },
+      Edit::MovePoints(moves) if moves.len() == 1 => "move a point",
+      Edit::MovePoints(moves) => return format!("move {} points", moves.len()),
+      Edit::SetStart { .. } => "change the start",
+      Edit::SetEnd { .. } => "change the end",
+      Edit::Batch(edits) if !edits.is_empty() && edits.iter().all(|edit| matches!(edit, Edit::RemovePoint { .. })) =>
+        return format!("remove {} points", edits.len()),
+      Edit::Batch(edits) if matches!(edits.as_slice(), [Edit::SetStart { .. }, Edit::SetEnd { .. }]) =>
+        "change the start and the end",
+      Edit::Batch(edits) => return format!("make {} edits", edits.len()),
+      Edit::ReplaceGraph { .. } => "change the whole graph",
+    });
+  }
+
+  fn undo(&self, graph: &mut DijkstraGraph)
+  {
+    match self
+    {
+      Edit::AddPoint { id, .. } => graph.remove_point(*id),
+      Edit::RemovePoint { id, x, y, edges, incoming } =>
+      {
+        graph.restore_point(*id, *x, *y, edges.clone());
+        incoming.iter()
+          .for_each(|(from, position, distance)| set_edge(graph, *from, *id, Some((*position, *distance))));
+      },
+      Edit::SetLines(changes) => changes.iter().rev()
+        .for_each(|change| set_edge(graph, change.from, change.to, change.before)),
+      Edit::MovePoints(moves) => moves.iter().for_each(|point_move| move_point(graph, point_move.id, point_move.from)),
+      Edit::SetStart { before, .. } => set_start(graph, *before),
+      Edit::SetEnd { before, .. } => set_end(graph, *before),
+      Edit::Batch(edits) => edits.iter().rev().for_each(|edit| edit.undo(graph)),
+      Edit::ReplaceGraph { before, .. } => graph.restore(before),
+    }
+  }
+
+  fn redo(&self, graph: &mut DijkstraGraph)
+  {
+    match self
+    {
+      Edit::AddPoint { id, x, y } => graph.restore_point(*id, *x, *y, vec![]),
+      Edit::RemovePoint { id, .. } => graph.remove_point(*id),
+      Edit::SetLines(changes) => changes.iter()
+        .for_each(|change| set_edge(graph, change.from, change.to, change.after)),
+      Edit::MovePoints(moves) => moves.iter().for_each(|point_move| move_point(graph, point_move.id, point_move.to)),
+      Edit::SetStart { after, .. } => set_start(graph, *after),
+      Edit::SetEnd { after, .. } => set_end(graph, *after),
+      Edit::Batch(edits) => edits.iter().for_each(|edit| edit.redo(graph)),
+      Edit::ReplaceGraph { after, .. } => graph.restore(after),
+    }
+  }
+}
+
+/// The position among the edges of `from` and the length of the edge from `from` to `to`, if it exists
+fn line_state(graph: &DijkstraGraph, from: usize, to: usize) -> Option<(usize, i32)>
+{
+  return graph.get(from)?.edges.iter().enumerate()
+    .find(|(_, edge)| edge.destination == to)
+    .map(|(position, edge)| (position, edge.distance));
+}
+
+/// Puts a single edge back into a recorded state, the opposite direction is left untouched
+fn set_edge(graph: &mut DijkstraGraph, from: usize, to: usize, state: Option<(usize, i32)>)
+{
+  let Some(point) = graph.get_mut(from) else { return; };
+  point.edges.retain(|edge| edge.destination != to);
+  if let Some((position, distance)) = state
+  { point.edges.insert(position.min(point.edges.len()), Edge { destination: to, distance }); }
+  graph.changed();
+}
+
+/// Removes a node and returns the edit that brings it back, `None` if it doesn't exist
+fn remove_point(graph: &mut DijkstraGraph, id: usize) -> Option<Edit>
+{
+  let point = graph.get(id)?;
+  let (x, y, edges) = (point.x, point.y, point.edges.clone());
+  let incoming = (0..graph.points().len())
+    .filter(|from| *from != id)
+    .filter_map(|from| line_state(graph, from, id).map(|(position, distance)| (from, position, distance)))
+    .collect();
+
+  graph.remove_point(id);
+  return Some(Edit::RemovePoint { id, x, y, edges, incoming });
+}
+
+fn move_point(graph: &mut DijkstraGraph, id: usize, (x, y): (f32, f32))
+{
+  let Some(point) = graph.get_mut(id) else { return; };
+  point.x = x;
+  point.y = y;
+}
+
+fn set_start(graph: &mut DijkstraGraph, start: Option<usize>)
+{
+  match start
+  {
+    Some(id) => graph.set_start(id),
+    None => graph.clear_start(),
+  }
+}
+
+fn set_end(graph: &mut DijkstraGraph, end: Option<usize>)
+{
+  match end
+  {
+    Some(id) => graph.set_end(id),
+    None => graph.clear_end(),
+  }
+}
+
+/// Returns true if both graphs have the same nodes at the same positions with the same lines, start and end
+fn is_same(a: &DijkstraGraph, b: &DijkstraGraph) -> bool
+{
+  return a.start() == b.start()
+    && a.end() == b.end()
+    && a.is_undirected() == b.is_undirected()
+    && a.points().len() == b.points().len()
+    && a.points().iter().zip(b.points()).all(|(a, b)| match (a, b)
+    {
+      (None, None) => true,
+      (Some(a), Some(b)) => a.x == b.x && a.y == b.y && a.edges == b.edges,
+      _ => false,
+    });
+}
+
+/// The edits of a graph that can be undone and redone. Every edit has to be made through the history to be recorded,
+/// its methods work like the ones of [`DijkstraGraph`] with the same name.
+#[derive(Default)]
+pub struct EditHistory
+{
+  /// The edits that can be undone, the most recent one last
+  done: VecDeque<Edit>,
+  /// The edits that have been undone, the most recently undone one last
+  undone: Vec<Edit>,
+  /// The nodes that are being moved around and where they have been before
+  moving: Vec<(usize, (f32, f32))>,
+}
+
+impl EditHistory
+{
+  /// Creates an empty history
+  pub fn new() -> EditHistory
+  { return EditHistory::default(); }
+
+  /// Records an edit that has been made to the graph. The edits that have been undone can't be redone anymore.
+  pub fn record(&mut self, edit: Edit)
+  {
+    self.undone.clear();
+    self.done.push_back(edit);
+    if self.done.len() > MAX_EDITS { self.done.pop_front(); }
+  }
+
+  /// Returns the edit that gets undone next
+  pub fn next_undo(&self) -> Option<&Edit>
+  { return self.done.back(); }
+
+  /// Returns the edit that gets redone next
+  pub fn next_redo(&self) -> Option<&Edit>
+  { return self.undone.last(); }
+
+  /// Undoes the most recent edit, returns false if there is nothing to undo
+  pub fn undo(&mut self, graph: &mut DijkstraGraph) -> bool
+  {
+    let Some(edit) = self.done.pop_back() else { return false; };
+    edit.undo(graph);
+    self.undone.push(edit);
+    return true;
+  }
+
+  /// Redoes the most recently undone edit, returns false if there is nothing to redo
+  pub fn redo(&mut self, graph: &mut DijkstraGraph) -> bool
+  {
+    let Some(edit) = self.undone.pop() else { return false; };
+    edit.redo(graph);
+    self.done.push_back(edit);
+    return true;
+  }
+
+  /// Inserts a node at the first free id
+  pub fn append_point(&mut self, graph: &mut DijkstraGraph, x: f32, y: f32)
+  {
+    let id = graph.points().iter()
+      .position(|node_option| node_option.is_none())
+      .unwrap_or(graph.points().len());
+
+    graph.append_point(x, y);
+    // Nothing is added to a full graph
+    if graph.get(id).is_some() { self.record(Edit::AddPoint { id, x, y }); }
+  }
+
+  /// Removes the node with the given id and all lines leading to it
+  pub fn remove_point(&mut self, graph: &mut DijkstraGraph, id: usize)
+  {
+    let Some(edit) = remove_point(graph, id) else { return; };
+    self.record(edit);
+  }
+
+  /// Removes the nodes with the given ids and all lines leading to them as a single edit
+  pub fn remove_points(&mut self, graph: &mut DijkstraGraph, ids: &[usize])
+  {
+    let edits: Vec<Edit> = ids.iter().filter_map(|id| remove_point(graph, *id)).collect();
+    if !edits.is_empty() { self.record(Edit::Batch(edits)); }
+  }
+
+  /// Adds a line or updates its length
+  pub fn add_line(&mut self, graph: &mut DijkstraGraph, from: usize, to: usize, distance: i32)
+  { self.change_line(graph, from, to, |graph| graph.add_line(from, to, distance)); }
+
+  /// Removes the line going from `from` to `to`
+  pub fn remove_line(&mut self, graph: &mut DijkstraGraph, from: usize, to: usize)
+  { self.change_line(graph, from, to, |graph| graph.remove_line(from, to)); }
+
+  /// Records how a change of a line has affected it, and the line in the opposite direction in an undirected graph
+  fn change_line(&mut self, graph: &mut DijkstraGraph, from: usize, to: usize, change: impl FnOnce(&mut DijkstraGraph))
+  {
+    let mut lines = vec![(from, to)];
+    if graph.is_undirected() && from != to { lines.push((to, from)); }
+    let before: Vec<Option<(usize, i32)>> = lines.iter().map(|(from, to)| line_state(graph, *from, *to)).collect();
+
+    change(graph);
+
+    let changes: Vec<LineChange> = lines.iter().zip(before)
+      .map(|((from, to), before)| LineChange { from: *from, to: *to, before, after: line_state(graph, *from, *to) })
+      .filter(|change| change.before != change.after)
+      .collect();
+    if !changes.is_empty() { self.record(Edit::SetLines(changes)); }
+  }
+
+  /// Moves the nodes to the given positions as a single edit, see [`DijkstraGraph::set_positions`]. Nodes that stay
+  /// where they are are left out of the edit.
+  pub fn set_positions(&mut self, graph: &mut DijkstraGraph, positions: &[Option<(f32, f32)>])
+  {
+    let moves: Vec<PointMove> = positions.iter().enumerate()
+      .filter_map(|(id, to)| Some(PointMove { id, from: graph.get(id).map(|point| (point.x, point.y))?, to: (*to)? }))
+      .filter(|point_move| point_move.from != point_move.to)
+      .collect();
+
+    graph.set_positions(positions);
+    if !moves.is_empty() { self.record(Edit::MovePoints(moves)); }
+  }
+
+  /// Remembers where the nodes are before they get moved around, the move is recorded by [`EditHistory::end_move`]
+  pub fn start_move(&mut self, graph: &DijkstraGraph, ids: &[usize])
+  {
+    self.moving = ids.iter()
+      .filter_map(|id| graph.get(*id).map(|point| (*id, (point.x, point.y))))
+      .collect();
+  }
+
+  /// Records the move of the nodes passed to [`EditHistory::start_move`] as a single edit, the ones that are back
+  /// where they have been are left out
+  pub fn end_move(&mut self, graph: &DijkstraGraph)
+  {
+    let moves: Vec<PointMove> = std::mem::take(&mut self.moving).into_iter()
+      .filter_map(|(id, from)| graph.get(id).map(|point| PointMove { id, from, to: (point.x, point.y) }))
+      .filter(|point_move| point_move.from != point_move.to)
+      .collect();
+    if !moves.is_empty() { self.record(Edit::MovePoints(moves)); }
+  }
+
+  /// Sets the node the path search starts at, `None` unsets it
+  pub fn set_start(&mut self, graph: &mut DijkstraGraph, start: Option<usize>)
+  {
+    let before = graph.start();
+    set_start(graph, start);
+    if before != start { self.record(Edit::SetStart { before, after: start }); }
+  }
+
+  /// Sets the node the path search ends at, `None` unsets it
+  pub fn set_end(&mut self, graph: &mut DijkstraGraph, end: Option<usize>)
+  {
+    let before = graph.end();
+    set_end(graph, end);
+    if before != end { self.record(Edit::SetEnd { before, after: end }); }
+  }
+
+  /// Sets the nodes the path search starts and ends at as a single edit, `None` unsets them
+  pub fn set_start_and_end(&mut self, graph: &mut DijkstraGraph, start: Option<usize>, end: Option<usize>)
+  {
+    let (start_before, end_before) = (graph.start(), graph.end());
+    set_start(graph, start);
+    set_end(graph, end);
+
+    let mut edits = vec![];
+    if start_before != start { edits.push(Edit::SetStart { before: start_before, after: start }); }
+    if end_before != end { edits.push(Edit::SetEnd { before: end_before, after: end }); }
+    match edits.len()
+    {
+      0 => {},
+      1 => self.record(edits.remove(0)),
+      _ => self.record(Edit::Batch(edits)),
+    }
+  }
+
+  /// Makes a change to the whole graph at once, like clearing it or replacing it with another one. Only the nodes,
+  /// lines, start and end and whether the graph is undirected are taken over, the settings of the graph are kept.
+  pub fn replace(&mut self, graph: &mut DijkstraGraph, change: impl FnOnce(&mut DijkstraGraph))
+  {
+    let mut after = graph.clone();
+    change(&mut after);
+    if is_same(graph, &after) { return; }
+
+    let before = graph.clone();
+    graph.restore(&after);
+    self.record(Edit::ReplaceGraph { before: Box::new(before), after: Box::new(after) });
+  }
+}
+
+// Tests
+#[path = "./tests/history_tests.rs"]
+#[cfg(test)]
+mod history_tests;
@@ -0,0 +1,185 @@
+//! Saving and loading graphs as JSON files.
+//!
+//! The format is versioned so that files written by older versions of this crate can still be read:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "points": [{ "id": 0, "x": 942.0, "y": 355.0 }, { "id": 1, "x": 720.0, "y": 208.0 }],
+//!   "lines": [{ "from": 0, "to": 1, "distance": 5 }],
+//!   "start": 0,
+//!   "end": 1,
+//!   "undirected": false
+//! }
+//! ```
+//!
+//! Undirected graphs only list every line once, files without `undirected` are directed.
+
+use std::{collections::HashSet, fmt::{self, Display}, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::DijkstraGraph;
+
+/// The version of the file format written by [`DijkstraGraph::to_json`]
+pub const JSON_VERSION: u32 = 1;
+
+/// Ids above this value are rejected; they would make the node store allocate an absurd amount of memory
+pub const MAX_JSON_ID: usize = 1_000_000;
+
+/// Everything that can go wrong while saving or loading a graph file
+#[derive(Debug)]
+pub enum JsonError
+{
+  /// The file could not be read or written
+  Io(std::io::Error),
+  /// The file is not valid JSON or doesn't have the expected structure
+  Syntax(serde_json::Error),
+  /// The file has been written by an unknown version of the format, versions start at 1
+  UnsupportedVersion(u32),
+  /// Two points share the same id
+  DuplicatePoint(usize),
+  /// A point id is larger than [`MAX_JSON_ID`]
+  IdTooLarge(usize),
+  /// A line, the start or the end refers to a point that isn't in the file
+  UnknownPoint(usize),
+}
+
+impl Display for JsonError
+{
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    return match self
+    {
+      JsonError::Io(error) => write!(formatter, "{}", error),
+      JsonError::Syntax(error) => write!(formatter, "Malformed graph file: {}", error),
+      JsonError::UnsupportedVersion(version) =>
+        write!(formatter, "Unsupported file version {} (supported are versions 1 to {})", version, JSON_VERSION),
+      JsonError::DuplicatePoint(id) => write!(formatter, "The point {} is defined more than once", id),
+      JsonError::IdTooLarge(id) => write!(formatter, "The point id {} is too large (at most {})", id, MAX_JSON_ID),
+      JsonError::UnknownPoint(id) => write!(formatter, "The point {} is used but never defined", id),
+    };
+  }
+}
+
+impl std::error::Error for JsonError {}
+
+impl From<std::io::Error> for JsonError
+{
+  fn from(error: std::io::Error) -> Self
+  { return JsonError::Io(error); }
+}
+
+impl From<serde_json::Error> for JsonError
+{
+  fn from(error: serde_json::Error) -> Self
+  { return JsonError::Syntax(error); }
+}
+
+#[derive(Serialize, Deserialize)]
+struct GraphFile
+{
+  version: u32,
+  points: Vec<PointEntry>,
+  lines: Vec<LineEntry>,
+  #[serde(default)]
+  start: Option<usize>,
+  #[serde(default)]
+  end: Option<usize>,
+  #[serde(default)]
+  undirected: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PointEntry
+{
+  id: usize,
+  x: f32,
+  y: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LineEntry
+{
+  from: usize,
+  to: usize,
+  distance: i32,
+}
+
+impl DijkstraGraph
+{
+  /// Serialises the points, lines, start and end of the graph and whether it is undirected into a pretty-printed
+  /// JSON string
+  pub fn to_json(&self) -> String
+  {
+    let file = GraphFile
+    {
+      version: JSON_VERSION,
+      points: self.points().iter().enumerate()
+        .filter_map(|(id, option)| option.as_ref().map(|point| PointEntry { id, x: point.x, y: point.y }))
+        .collect(),
+      lines: self.lines().iter()
+        // The other direction of an undirected line is implied
+        .filter(|(from, _, _, to, _)| !self.is_undirected() || from <= to)
+        .map(|(from, _, distance, to, _)| LineEntry { from: *from, to: *to, distance: *distance })
+        .collect(),
+      start: self.start(),
+      end: self.end(),
+      undirected: self.is_undirected(),
+    };
+
+    // Serialising plain structs into a string can't fail
+    return serde_json::to_string_pretty(&file).unwrap();
+  }
+
+  /// Parses a graph from a JSON string that has been created by [`DijkstraGraph::to_json`].
+  ///
+  /// The returned graph has no node limit.
+  pub fn from_json(json: &str) -> Result<DijkstraGraph, JsonError>
+  {
+    let file: GraphFile = serde_json::from_str(json)?;
+
+    if file.version == 0 || file.version > JSON_VERSION { return Err(JsonError::UnsupportedVersion(file.version)); }
+
+    let mut ids = HashSet::new();
+    for point in file.points.iter()
+    {
+      if point.id > MAX_JSON_ID { return Err(JsonError::IdTooLarge(point.id)); }
+      if !ids.insert(point.id) { return Err(JsonError::DuplicatePoint(point.id)); }
+    }
+
+    let used_ids = file.lines.iter()
+      .flat_map(|line| [line.from, line.to])
+      .chain(file.start)
+      .chain(file.end);
+    for id in used_ids
+    {
+      if !ids.contains(&id) { return Err(JsonError::UnknownPoint(id)); }
+    }
+
+    let mut graph = DijkstraGraph::new();
+    graph.set_undirected(file.undirected);
+    file.points.iter().for_each(|point| graph.add_point(point.id, point.x, point.y));
+    file.lines.iter().for_each(|line| graph.add_line(line.from, line.to, line.distance));
+    if let Some(start) = file.start { graph.set_start(start); }
+    if let Some(end) = file.end { graph.set_end(end); }
+
+    return Ok(graph);
+  }
+
+  /// Writes the graph to a JSON file, see [`DijkstraGraph::to_json`]
+  pub fn save(&self, path: impl AsRef<Path>) -> Result<(), JsonError>
+  {
+    fs::write(path, self.to_json())?;
+    return Ok(());
+  }
+
+  /// Reads a graph from a JSON file, see [`DijkstraGraph::from_json`]
+  pub fn load(path: impl AsRef<Path>) -> Result<DijkstraGraph, JsonError>
+  { return DijkstraGraph::from_json(&fs::read_to_string(path)?); }
+}
+
+// Tests
+#[path = "./tests/json_tests.rs"]
+#[cfg(test)]
+mod json_tests;
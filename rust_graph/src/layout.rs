@@ -0,0 +1,303 @@
+//! Automatic layouts that place the nodes of a graph.
+//!
+//! The deterministic layouts place the nodes on a circle, a grid or in layers and return the new positions instead of
+//! moving the nodes, so they can be animated or applied with [`DijkstraGraph::set_positions`]. They spread the nodes
+//! over an area given by its top left corner and its size.
+//!
+//! The force-directed layout follows Fruchterman and Reingold: every pair of nodes pushes each other away, every line
+//! pulls its two nodes together like a spring, and a weak pull towards the center keeps unconnected parts from
+//! drifting apart. The direction of the lines is ignored. Each step moves the nodes at most by the current
+//! temperature, which cools down over the course of a layout so that the nodes settle. Every step compares all pairs
+//! of nodes, so it gets slow for graphs with thousands of nodes.
+
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use crate::{DijkstraGraph, TraversalKind};
+
+/// How strongly every node is pulled towards the center of all nodes, in proportion to its distance from there
+const GRAVITY: f32 = 0.002;
+
+/// How often the layered layout reorders its layers to untangle crossing lines
+const SWEEPS: usize = 4;
+
+/// The position of every node as `(x, y)`, indexed by id; `None` for removed nodes
+pub type Positions = Vec<Option<(f32, f32)>>;
+
+/// How the layered layout puts the nodes into layers
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Layering
+{
+  /// By the amount of lines a breadth-first search from the start needs to reach them, the nodes it can't reach come
+  /// last
+  Depth,
+  /// By their topological rank, every node comes after all nodes with lines to it. The nodes of a cycle share a layer
+  /// with the rest of their strongly connected component.
+  Rank,
+}
+
+/// The settings of the force-directed layout
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ForceLayout
+{
+  /// The distance two connected nodes settle at if nothing else pushes them
+  pub spring_length: f32,
+  /// Whether longer lines get longer springs. The spring of the average line has `spring_length`, the others are
+  /// longer or shorter in proportion to their length; the sign of the length is ignored.
+  pub weighted: bool,
+}
+
+impl Default for ForceLayout
+{
+  fn default() -> Self
+  { return ForceLayout { spring_length: 100., weighted: false }; }
+}
+
+impl DijkstraGraph
+{
+  /// Returns the position of every node
+  pub fn positions(&self) -> Positions
+  { return self.points().iter().map(|point| point.as_ref().map(|point| (point.x, point.y))).collect(); }
+
+  /// Moves the nodes to the given positions, nodes without one stay where they are. Like any other move this doesn't
+  /// change the revision.
+  pub fn set_positions(&mut self, positions: &[Option<(f32, f32)>])
+  {
+    for (id, position) in positions.iter().enumerate()
+    {
+      let (Some((x, y)), Some(point)) = (position, self.get_mut(id)) else { continue; };
+      point.x = *x;
+      point.y = *y;
+    }
+  }
+
+  /// The ids of all nodes in ascending order
+  fn ids(&self) -> Vec<usize>
+  { return (0..self.points().len()).filter(|id| self.get(*id).is_some()).collect(); }
+
+  /// Places the nodes on the largest circle that fits into the area, ordered by id clockwise from the top
+  pub fn circle_layout(&self, (left, top): (f32, f32), (width, height): (f32, f32)) -> Positions
+  {
+    let ids = self.ids();
+    let center = (left + width / 2., top + height / 2.);
+    // A single node sits in the middle
+    let radius = if ids.len() > 1 { width.min(height) / 2. } else { 0. };
+
+    let mut positions = vec![None; self.points().len()];
+    for (index, id) in ids.iter().enumerate()
+    {
+      let angle = -FRAC_PI_2 + TAU * index as f32 / ids.len() as f32;
+      positions[*id] = Some((center.0 + radius * angle.cos(), center.1 + radius * angle.sin()));
+    }
+    return positions;
+  }
+
+  /// Places the nodes on a grid ordered by id, row by row. The grid has about as many columns per row as the area is
+  /// wider than high, so the nodes are spread evenly.
+  pub fn grid_layout(&self, (left, top): (f32, f32), (width, height): (f32, f32)) -> Positions
+  {
+    let ids = self.ids();
+    let mut positions = vec![None; self.points().len()];
+    if ids.is_empty() { return positions; }
+
+    let columns = ((ids.len() as f32 * width / height).sqrt().ceil() as usize).clamp(1, ids.len());
+    let rows = ids.len().div_ceil(columns);
+    for (index, id) in ids.iter().enumerate()
+    {
+      let (column, row) = (index % columns, index / columns);
+      positions[*id] = Some((
+        left + (column as f32 + 0.5) * width / columns as f32,
+        top + (row as f32 + 0.5) * height / rows as f32,
+      ));
+    }
+    return positions;
+  }
+
+  /// Places the nodes in columns from left to right by their layer, in the style of Sugiyama. The nodes of every
+  /// column are ordered to avoid crossing lines. Layering by depth needs a start, otherwise `None` is returned.
+  pub fn layered_layout(&self, layering: Layering, (left, top): (f32, f32), (width, height): (f32, f32)) -> Option<Positions>
+  {
+    let amount = self.points().len();
+    let layer_of: Vec<Option<usize>> = match layering
+    {
+      Layering::Depth =>
+      {
+        let start = self.start().filter(|start| self.get(*start).is_some())?;
+        let levels = self.traverse(start, TraversalKind::BreadthFirst).levels;
+        let unreachable = levels.iter().flatten().max().map_or(0, |level| level + 1);
+        (0..amount).map(|id| self.get(id).map(|_| levels[id].unwrap_or(unreachable))).collect()
+      },
+      Layering::Rank =>
+      {
+        // The components are collapsed into nodes, which leaves no cycles
+        let components = self.strongly_connected_components();
+        let ranks = self.condensation(&components).topological_order().ok()?.ranks;
+        (0..amount).map(|id| components.component_of[id].and_then(|component| ranks[component])).collect()
+      },
+    };
+
+    let mut layers: Vec<Vec<usize>> = vec![vec![]; layer_of.iter().flatten().max().map_or(0, |layer| layer + 1)];
+    (0..amount).for_each(|id| if let Some(layer) = layer_of[id] { layers[layer].push(id); });
+    self.reduce_crossings(&mut layers, &layer_of);
+
+    let mut positions = vec![None; amount];
+    for (column, layer) in layers.iter().enumerate()
+    {
+      for (row, id) in layer.iter().enumerate()
+      {
+        positions[*id] = Some((
+          left + (column as f32 + 0.5) * width / layers.len() as f32,
+          top + (row as f32 + 0.5) * height / layer.len() as f32,
+        ));
+      }
+    }
+    return Some(positions);
+  }
+
+  /// Sorts the nodes of every layer by the average position of their neighbours in the layer before it, then in the
+  /// layer after it, and so on. This is the barycenter heuristic, it untangles most crossing lines.
+  fn reduce_crossings(&self, layers: &mut [Vec<usize>], layer_of: &[Option<usize>])
+  {
+    let mut neighbours = vec![vec![]; self.points().len()];
+    self.lines().iter()
+      .filter(|(from, _, _, to, _)| from != to)
+      .for_each(|(from, _, _, to, _)|
+      {
+        neighbours[*from].push(*to);
+        neighbours[*to].push(*from);
+      });
+
+    // The position of every node within its layer, from 0 at the top to 1 at the bottom
+    let mut positions = vec![0_f32; self.points().len()];
+    let place = |layer: &[usize], positions: &mut Vec<f32>|
+      layer.iter().enumerate().for_each(|(row, id)| positions[*id] = (row as f32 + 0.5) / layer.len() as f32);
+    layers.iter().for_each(|layer| place(layer, &mut positions));
+
+    for sweep in 0..SWEEPS
+    {
+      let downwards = sweep % 2 == 0;
+      let order: Vec<usize> = if downwards { (1..layers.len()).collect() } else { (0..layers.len().saturating_sub(1)).rev().collect() };
+
+      for layer in order
+      {
+        let adjacent = if downwards { layer - 1 } else { layer + 1 };
+        let mut barycenters: Vec<(f32, usize)> = layers[layer].iter()
+          .map(|id|
+          {
+            let adjacent_positions: Vec<f32> = neighbours[*id].iter()
+              .filter(|neighbour| layer_of[**neighbour] == Some(adjacent))
+              .map(|neighbour| positions[*neighbour])
+              .collect();
+            // Nodes without neighbours there keep their place
+            if adjacent_positions.is_empty() { return (positions[*id], *id); }
+            return (adjacent_positions.iter().sum::<f32>() / adjacent_positions.len() as f32, *id);
+          })
+          .collect();
+        barycenters.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        layers[layer] = barycenters.into_iter().map(|(_, id)| id).collect();
+        place(&layers[layer], &mut positions);
+      }
+    }
+  }
+
+  /// Moves every node that isn't pinned by one step of the force-directed layout, at most by `temperature`. Pinned
+  /// nodes still push and pull the others. Returns how far the nodes have moved in total.
+  pub fn force_layout_step(&mut self, layout: &ForceLayout, temperature: f32, pinned: &[usize]) -> f32
+  {
+    let ids = self.ids();
+    if ids.is_empty() { return 0.; }
+
+    // The index of every node in `ids`, indexed by id
+    let mut indices = vec![usize::MAX; self.points().len()];
+    ids.iter().enumerate().for_each(|(index, id)| indices[*id] = index);
+
+    let positions: Vec<(f32, f32)> = ids.iter().map(|id| { let point = self.get(*id).unwrap(); (point.x, point.y) }).collect();
+    let mut displacements = vec![(0_f32, 0_f32); ids.len()];
+    let k = layout.spring_length;
+
+    // Every pair of nodes pushes each other away
+    for a in 0..ids.len()
+    {
+      for b in (a + 1)..ids.len()
+      {
+        let (direction, distance) = direction(positions[a], positions[b], a + b);
+        let force = k * k / distance;
+        displacements[a].0 += direction.0 * force;
+        displacements[a].1 += direction.1 * force;
+        displacements[b].0 -= direction.0 * force;
+        displacements[b].1 -= direction.1 * force;
+      }
+    }
+
+    // Every line pulls its nodes together
+    let lines = self.undirected_lines();
+    let average = lines.iter().map(|(_, _, distance)| distance.unsigned_abs().max(1) as f32).sum::<f32>() / lines.len().max(1) as f32;
+    for (from, to, length) in lines.iter()
+    {
+      let (a, b) = (indices[*from], indices[*to]);
+      let spring_length = if layout.weighted { k * length.unsigned_abs().max(1) as f32 / average } else { k };
+      let (direction, distance) = direction(positions[a], positions[b], a + b);
+      // Balances the push between the two nodes at the length of the spring, for unweighted springs this is d² / k
+      let force = distance * distance * k * k / spring_length.powi(3);
+      displacements[a].0 -= direction.0 * force;
+      displacements[a].1 -= direction.1 * force;
+      displacements[b].0 += direction.0 * force;
+      displacements[b].1 += direction.1 * force;
+    }
+
+    let center = positions.iter().fold((0., 0.), |(x, y), position| (x + position.0, y + position.1));
+    let center = (center.0 / ids.len() as f32, center.1 / ids.len() as f32);
+
+    let mut moved = 0.;
+    for (index, id) in ids.iter().enumerate()
+    {
+      if pinned.contains(id) { continue; }
+
+      let (x, y) = positions[index];
+      let displacement = (
+        displacements[index].0 + (center.0 - x) * GRAVITY * k,
+        displacements[index].1 + (center.1 - y) * GRAVITY * k,
+      );
+      let length = (displacement.0 * displacement.0 + displacement.1 * displacement.1).sqrt();
+      if length <= f32::EPSILON { continue; }
+
+      let step = length.min(temperature);
+      let point = self.get_mut(*id).unwrap();
+      point.x += displacement.0 / length * step;
+      point.y += displacement.1 / length * step;
+      moved += step;
+    }
+
+    return moved;
+  }
+
+  /// Runs the force-directed layout for the given amount of steps, starting from the current positions. The
+  /// temperature starts at the spring length and cools down to nothing.
+  pub fn force_layout(&mut self, layout: &ForceLayout, steps: usize, pinned: &[usize])
+  {
+    for step in 0..steps
+    {
+      let temperature = layout.spring_length * (1. - step as f32 / steps as f32);
+      self.force_layout_step(layout, temperature, pinned);
+    }
+  }
+}
+
+/// The unit vector pointing from `b` to `a` and the distance between them. Nodes on top of each other are pushed
+/// apart in a direction that depends on `seed`, so that they don't all leave in the same direction.
+fn direction(a: (f32, f32), b: (f32, f32), seed: usize) -> ((f32, f32), f32)
+{
+  let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+  let distance = (dx * dx + dy * dy).sqrt();
+  if distance < 0.01
+  {
+    let angle = seed as f32;
+    return ((angle.cos(), angle.sin()), 0.01);
+  }
+  return ((dx / distance, dy / distance), distance);
+}
+
+// Tests
+#[path = "./tests/layout_tests.rs"]
+#[cfg(test)]
+mod layout_tests;
@@ -0,0 +1,55 @@
+//! # Rust Graph
+//!
+//! The graph model and algorithms behind the Rust Graph Visualiser. It has no dependency on any rendering or UI
+//! library, so graphs can be built, queried and tested in plain Rust.
+//!
+//! ```
+//! use rust_graph::DijkstraGraph;
+//!
+//! let mut graph = DijkstraGraph::new();
+//! graph.append_point(100., 100.);
+//! graph.append_point(200., 100.);
+//! graph.append_point(200., 200.);
+//!
+//! graph.add_line(0, 1, 4);
+//! graph.add_line(1, 2, 3);
+//! graph.add_line(0, 2, 9);
+//!
+//! graph.set_start(0);
+//! graph.set_end(2);
+//! graph.find_shortest_path();
+//!
+//! assert_eq!(graph.get_path(), Some(vec![0, 1, 2]));
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod a_star;
+pub mod all_pairs;
+pub mod bellman_ford;
+pub mod components;
+pub mod dot;
+pub mod flow;
+pub mod geometry;
+pub mod graph;
+pub mod history;
+pub mod json;
+pub mod layout;
+pub mod search;
+pub mod shortest_paths;
+pub mod spanning_tree;
+pub mod topological;
+pub mod traversal;
+
+pub use all_pairs::{Distance, DistanceMatrix};
+pub use components::Components;
+pub use dot::DotError;
+pub use flow::{AugmentingPath, FlowLine, MaxFlow};
+pub use graph::{DijkstraGraph, DijkstraNode, Edge};
+pub use history::{Edit, EditHistory};
+pub use json::JsonError;
+pub use layout::{ForceLayout, Layering, Positions};
+pub use search::{DijkstraSearch, PathAlgorithm, SearchEvent, TieBreak};
+pub use spanning_tree::{SpanningForest, SpanningTreeAlgorithm};
+pub use topological::{CriticalPath, TopologicalOrder};
+pub use traversal::{LineKind, Traversal, TraversalKind};
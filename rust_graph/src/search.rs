@@ -0,0 +1,347 @@
+//! Dijkstra's shortest path algorithm, or A*, as a state machine that can be advanced one event at a time.
+//!
+//! ```
+//! use rust_graph::{DijkstraGraph, search::{DijkstraSearch, SearchEvent}};
+//!
+//! let mut graph = DijkstraGraph::new();
+//! graph.insert_small_graph();
+//! graph.set_start(2);
+//! graph.set_end(4);
+//!
+//! let mut search = DijkstraSearch::new(&mut graph).unwrap();
+//! assert_eq!(search.step(&mut graph), Some(SearchEvent::Pop { node: 2, distance: 0 }));
+//!
+//! while search.step(&mut graph).is_some() {}
+//! assert_eq!(graph.get_path(), Some(vec![2, 3, 4]));
+//! ```
+
+use std::{cmp::Reverse, collections::{BinaryHeap, VecDeque}};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{a_star::heuristic, DijkstraGraph, Edge};
+
+/// Decides which parent a node keeps when it can be reached over two different lines with the same distance
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TieBreak
+{
+  /// The parent with the lowest id wins
+  #[default]
+  LowestId,
+  /// The parent that is reached over fewer lines from the start wins, if that is a tie as well the lowest id wins
+  FewestHops,
+  /// A coin flip decides; the same seed always leads to the same path
+  SeededRandom(u64),
+}
+
+/// The algorithm used to search the shortest path
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum PathAlgorithm
+{
+  /// Visits the nodes in the order of their distance from the start
+  #[default]
+  Dijkstra,
+  /// Visits the nodes in the order of their distance from the start plus their estimated distance to the end. The
+  /// estimate is the straight line distance between the two nodes on the screen, multiplied by `scale`.
+  AStar
+  {
+    /// Converts screen distances into line lengths
+    scale: f32,
+  },
+  /// Relaxes every line as often as needed, which also works with negative lines and finds cycles of negative
+  /// length. It can't be run step by step and always keeps the first parent it finds for a node.
+  BellmanFord,
+}
+
+/// A single thing that happened while searching the shortest path
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SearchEvent
+{
+  /// The node with the smallest tentative distance (plus its estimated distance to the end for A*) has been taken
+  /// from the frontier
+  Pop
+  {
+    /// The id of the node
+    node: usize,
+    /// Its tentative distance, which is final from now on
+    distance: i32,
+  },
+  /// An edge of the current node is looked at
+  Relax
+  {
+    /// The current node
+    from: usize,
+    /// The node the edge points to
+    to: usize,
+    /// The distance `to` would have if it was reached over this edge
+    distance: i32,
+  },
+  /// A node has received a new tentative distance
+  Update
+  {
+    /// The id of the node
+    node: usize,
+    /// Its new tentative distance
+    distance: i32,
+    /// The node it is reached from
+    parent: usize,
+  },
+}
+
+/// ### Dijkstra Search
+///
+/// Runs Dijkstra's algorithm or A* (see [`PathAlgorithm`]) on a [`DijkstraGraph`] one [`SearchEvent`] at a time. The tentative distances, parents
+/// and visited flags are written into the nodes of the graph as the search progresses, so the graph can be
+/// inspected (or drawn) between two steps.
+///
+/// Every edit of the graph resets these values, which stops the search.
+#[derive(Clone, Debug)]
+pub struct DijkstraSearch
+{
+  start: usize,
+  end: usize,
+  /// The nodes that have been reached but not visited yet, ordered by their tentative distance plus their heuristic
+  /// (lowest first), then by their tentative distance and then by their id.
+  ///
+  /// A node is pushed again every time its distance changes, outdated entries are skipped when popping.
+  unvisited_points: BinaryHeap<Reverse<(i64, i32, usize)>>,
+  /// The node whose edges are currently being relaxed
+  current: Option<usize>,
+  /// The edges of the current node that haven't been relaxed yet
+  pending_edges: VecDeque<Edge>,
+  /// A distance update that has been decided on in the last relaxation and is applied in the next step
+  pending_update: Option<(usize, i32, usize)>,
+  /// The length of the shortest path to the end found so far; longer paths are not explored anymore
+  possible_path_length: i32,
+  finished: bool,
+  tie_break: TieBreak,
+  /// Flips the coins for [`TieBreak::SeededRandom`]
+  rng: StdRng,
+  /// The amount of lines between the start and every node, indexed by id
+  hops: Vec<u32>,
+  /// The estimated distance from every node to the end, indexed by id; all zeros for Dijkstra's algorithm
+  heuristic: Vec<u32>,
+  /// The amount of nodes that have been taken from the frontier
+  expanded: usize,
+  /// A* can reach a visited node over a shorter path if its heuristic isn't consistent, then it has to be visited
+  /// again. With negative lines this could go on forever, so it is only done if there are none.
+  reopen_visited: bool,
+}
+
+impl DijkstraSearch
+{
+  /// Prepares a search from the start to the end node of the graph and clears the previous result.
+  ///
+  /// Returns `None` if either the start or the end is not set, or if the graph uses [`PathAlgorithm::BellmanFord`].
+  /// If the start or end refer to nodes that don't exist anymore they get unset.
+  pub fn new(graph: &mut DijkstraGraph) -> Option<DijkstraSearch>
+  {
+    if graph.path_algorithm() == PathAlgorithm::BellmanFord { return None; }
+    let (start, end) = (graph.start()?, graph.end()?);
+    if graph.get(start).is_none() { graph.clear_start(); return None; }
+    if graph.get(end).is_none() { graph.clear_end(); return None; }
+
+    graph.clear_path();
+    graph.searched = true;
+
+    let start_point = graph.get_mut(start).unwrap();
+    start_point.distance = Some(0);
+    start_point.parent = Some(start);
+
+    let mut hops = vec![u32::MAX; graph.points().len()];
+    hops[start] = 0;
+
+    let seed = match graph.tie_break() { TieBreak::SeededRandom(seed) => seed, _ => 0 };
+
+    let end_point = graph.get(end).unwrap();
+    let heuristic = graph.points().iter()
+      .map(|point_option| match (graph.path_algorithm(), point_option)
+      {
+        (PathAlgorithm::AStar { scale }, Some(point)) => heuristic(point, end_point, scale),
+        _ => 0,
+      })
+      .collect();
+
+    return Some(DijkstraSearch
+    {
+      start,
+      end,
+      unvisited_points: BinaryHeap::from([Reverse((0, 0, start))]),
+      current: None,
+      pending_edges: VecDeque::new(),
+      pending_update: None,
+      possible_path_length: i32::MAX,
+      finished: false,
+      tie_break: graph.tie_break(),
+      rng: StdRng::seed_from_u64(seed),
+      hops,
+      heuristic,
+      expanded: 0,
+      reopen_visited: matches!(graph.path_algorithm(), PathAlgorithm::AStar { .. }) && !graph.has_negative_lines(),
+    });
+  }
+
+  /// The node whose edges are currently being relaxed
+  pub fn current(&self) -> Option<usize>
+  { return self.current; }
+
+  /// The nodes that have been reached but not visited yet, sorted by id
+  pub fn frontier(&self, graph: &DijkstraGraph) -> Vec<usize>
+  {
+    let mut frontier: Vec<usize> = self.unvisited_points.iter()
+      .map(|Reverse((_, _, id))| *id)
+      .filter(|id| graph.get(*id).is_some_and(|point| !point.visited))
+      .collect();
+    frontier.sort_unstable();
+    frontier.dedup();
+    return frontier;
+  }
+
+  /// The amount of nodes that have been taken from the frontier so far; a node can be counted more than once if A*
+  /// has to visit it again
+  pub fn expanded(&self) -> usize
+  { return self.expanded; }
+
+  /// Returns true once the search has run to completion
+  pub fn is_finished(&self) -> bool
+  { return self.finished; }
+
+  /// Returns false if the graph has been edited since the search started
+  pub fn is_valid(&self, graph: &DijkstraGraph) -> bool
+  {
+    return graph.start() == Some(self.start)
+      && graph.end() == Some(self.end)
+      && graph.get(self.start).is_some_and(|point| point.distance == Some(0));
+  }
+
+  /// Advances the search by one event; returns `None` once the search is over
+  pub fn step(&mut self, graph: &mut DijkstraGraph) -> Option<SearchEvent>
+  {
+    if self.finished { return None; }
+    if !self.is_valid(graph)
+    {
+      self.finish();
+      return None;
+    }
+
+    if let Some((node, distance, parent)) = self.pending_update.take()
+    {
+      if let Some(point) = graph.get_mut(node)
+      {
+        // Only a shorter distance reopens a visited node, a tie merely changes its parent
+        if point.distance.is_none_or(|old_distance| old_distance > distance) { point.visited = false; }
+        point.distance = Some(distance);
+        point.parent = Some(parent);
+        self.hops[node] = self.hops[parent].saturating_add(1);
+      }
+      return Some(SearchEvent::Update { node, distance, parent });
+    }
+
+    if let Some(current_id) = self.current
+    {
+      while let Some(edge) = self.pending_edges.pop_front()
+      {
+        if let Some(event) = self.relax(graph, current_id, edge) { return Some(event); }
+      }
+      self.current = None;
+    }
+
+    return self.pop(graph);
+  }
+
+  /// Takes the node with the lowest tentative distance (plus heuristic) out of the frontier
+  fn pop(&mut self, graph: &mut DijkstraGraph) -> Option<SearchEvent>
+  {
+    while let Some(Reverse((estimate, distance, current_id))) = self.unvisited_points.pop()
+    {
+      // No path over this node can be shorter than the one that has already been found (as long as the heuristic
+      // never overestimates), and the same is true for the rest of the frontier
+      if estimate > self.possible_path_length as i64 { break; }
+
+      let Some(current_point) = graph.get_mut(current_id) else { continue; };
+
+      // The frontier contains an entry for every distance a node has had, only the latest one counts
+      if current_point.visited || current_point.distance != Some(distance) { continue; }
+      current_point.visited = true;
+      self.expanded += 1;
+
+      // The end is never expanded, it only limits how far the search has to go
+      if current_id == self.end
+      {
+        if distance < self.possible_path_length { self.possible_path_length = distance; }
+        return Some(SearchEvent::Pop { node: current_id, distance });
+      }
+
+      self.current = Some(current_id);
+      self.pending_edges = current_point.edges.iter().copied().collect();
+
+      return Some(SearchEvent::Pop { node: current_id, distance });
+    }
+
+    self.finish();
+    return None;
+  }
+
+  /// Looks at one edge of the current node; returns `None` if the edge is skipped
+  fn relax(&mut self, graph: &mut DijkstraGraph, current_id: usize, edge: Edge) -> Option<SearchEvent>
+  {
+    // A self-loop can never lead to a shorter path, one of length zero would make the node its own parent
+    if edge.destination == current_id { return None; }
+
+    let current_distance = graph.get(current_id)?.distance?;
+    let possibly_lower_goal = current_distance.saturating_add(edge.distance);
+
+    if possibly_lower_goal > self.possible_path_length { return None; }
+
+    let neighbour = graph.get(edge.destination)?;
+
+    let is_shorter = neighbour.distance.is_none_or(|distance| distance > possibly_lower_goal);
+
+    // Dijkstra's algorithm assumes that the distance of a visited node is final, which negative lines break. Nothing
+    // is done about it, that is exactly why it can't handle them.
+    if is_shorter && neighbour.visited && !self.reopen_visited
+    { return Some(SearchEvent::Relax { from: current_id, to: edge.destination, distance: possibly_lower_goal }); }
+
+    if is_shorter
+    {
+      let estimate = possibly_lower_goal as i64 + self.heuristic[edge.destination] as i64;
+      self.unvisited_points.push(Reverse((estimate, possibly_lower_goal, edge.destination)));
+    }
+
+    // The distance of a visited node is final, giving it another parent could make it a descendant of itself over
+    // lines of length zero
+    let is_tie = neighbour.distance == Some(possibly_lower_goal) && !neighbour.visited;
+    if is_shorter || (is_tie && self.wins_tie(current_id, neighbour.parent))
+    { self.pending_update = Some((edge.destination, possibly_lower_goal, current_id)); }
+
+    return Some(SearchEvent::Relax { from: current_id, to: edge.destination, distance: possibly_lower_goal });
+  }
+
+  /// Returns true if `challenger` should replace `parent` as the parent of a node with an equal distance
+  fn wins_tie(&mut self, challenger: usize, parent: Option<usize>) -> bool
+  {
+    let Some(parent) = parent else { return true; };
+    if challenger == parent { return false; }
+
+    return match self.tie_break
+    {
+      TieBreak::LowestId => challenger < parent,
+      TieBreak::FewestHops =>
+        (self.hops[challenger], challenger) < (self.hops[parent], parent),
+      TieBreak::SeededRandom(_) => self.rng.gen(),
+    };
+  }
+
+  fn finish(&mut self)
+  {
+    self.finished = true;
+    self.current = None;
+    self.pending_edges.clear();
+    self.pending_update = None;
+  }
+}
+
+// Tests
+#[path = "./tests/search_tests.rs"]
+#[cfg(test)]
+mod search_tests;
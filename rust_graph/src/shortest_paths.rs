@@ -0,0 +1,185 @@
+//! Finding every shortest path between the start and the end instead of only one of them.
+//!
+//! All lines `a → b` with `distance(a) + length = distance(b)` are the shortest path lines of the start node. Every
+//! simple path along these lines from the start to the end is a shortest path. Without lines of length zero they form
+//! a DAG, lines of length zero can add cycles to it.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::DijkstraGraph;
+
+impl DijkstraGraph
+{
+  /// Returns the length of the shortest path from `start` to every node, indexed by id.
+  ///
+  /// Unreachable and missing nodes are `None`. Unlike [`DijkstraGraph::find_shortest_path`] this doesn't change
+  /// the state of the nodes. If there are negative lines Bellman-Ford is used, and if a cycle of negative length can
+  /// be reached from `start` all distances are `None`.
+  pub fn distances_from(&self, start: usize) -> Vec<Option<i64>>
+  {
+    if self.has_negative_lines()
+    { return self.bellman_ford(start).unwrap_or_else(|_| vec![None; self.points().len()]); }
+
+    let mut distances = vec![None; self.points().len()];
+    if self.get(start).is_none() { return distances; }
+
+    distances[start] = Some(0);
+    let mut frontier = BinaryHeap::from([Reverse((0_i64, start))]);
+
+    while let Some(Reverse((distance, id))) = frontier.pop()
+    {
+      if distances[id] != Some(distance) { continue; }
+
+      for edge in self.get(id).unwrap().edges()
+      {
+        if self.get(edge.destination()).is_none() { continue; }
+
+        let new_distance = distance + edge.distance() as i64;
+        if distances[edge.destination()].is_none_or(|old_distance| old_distance > new_distance)
+        {
+          distances[edge.destination()] = Some(new_distance);
+          frontier.push(Reverse((new_distance, edge.destination())));
+        }
+      }
+    }
+
+    return distances;
+  }
+
+  /// Returns the length of the shortest path from every node to `end`, indexed by id.
+  ///
+  /// Nodes that can't reach the end and missing nodes are `None`. Negative lines are handled like in
+  /// [`DijkstraGraph::distances_from`].
+  pub fn distances_to(&self, end: usize) -> Vec<Option<i64>>
+  {
+    if self.has_negative_lines()
+    { return self.bellman_ford_to(end).unwrap_or_else(|_| vec![None; self.points().len()]); }
+
+    let mut distances = vec![None; self.points().len()];
+    if self.get(end).is_none() { return distances; }
+
+    let mut incoming_lines = vec![vec![]; self.points().len()];
+    self.lines().iter()
+      .for_each(|(from, _, distance, to, _)| incoming_lines[*to].push((*from, *distance as i64)));
+
+    distances[end] = Some(0);
+    let mut frontier = BinaryHeap::from([Reverse((0_i64, end))]);
+
+    while let Some(Reverse((distance, id))) = frontier.pop()
+    {
+      if distances[id] != Some(distance) { continue; }
+
+      for (from, length) in incoming_lines[id].iter()
+      {
+        let new_distance = distance + length;
+        if distances[*from].is_none_or(|old_distance| old_distance > new_distance)
+        {
+          distances[*from] = Some(new_distance);
+          frontier.push(Reverse((new_distance, *from)));
+        }
+      }
+    }
+
+    return distances;
+  }
+
+  /// Returns all lines `(from, to)` that lie on at least one shortest walk from the start to the end, sorted.
+  ///
+  /// Lines of length zero can form cycles among them, then some lines are only part of shortest walks that go around
+  /// such a cycle and not of any simple path; [`DijkstraGraph::all_shortest_paths`] leaves those out. Returns an
+  /// empty list if the start or the end isn't set or if there is no path between them.
+  pub fn shortest_path_lines(&self) -> Vec<(usize, usize)>
+  {
+    let (Some(start), Some(end)) = (self.start(), self.end()) else { return vec![]; };
+    let distances = self.distances_from(start);
+    if distances.get(end).copied().flatten().is_none() { return vec![]; }
+
+    let mut tight_predecessors = vec![vec![]; distances.len()];
+    self.lines().iter()
+      .for_each(|(from, _, distance, to, _)|
+      {
+        if let (Some(from_distance), Some(to_distance)) = (distances[*from], distances[*to])
+        {
+          if from_distance + *distance as i64 == to_distance { tight_predecessors[*to].push(*from); }
+        }
+      });
+
+    // Walking backwards from the end keeps only the lines that actually lead to it
+    let mut leads_to_end = vec![false; distances.len()];
+    leads_to_end[end] = true;
+    let mut stack = vec![end];
+    let mut lines = vec![];
+
+    while let Some(to) = stack.pop()
+    {
+      for from in tight_predecessors[to].iter()
+      {
+        lines.push((*from, to));
+        if !leads_to_end[*from]
+        {
+          leads_to_end[*from] = true;
+          stack.push(*from);
+        }
+      }
+    }
+
+    lines.sort_unstable();
+    lines.dedup();
+    return lines;
+  }
+
+  /// Returns every shortest path from the start to the end, but at most `limit` of them.
+  ///
+  /// The paths are sorted by the ids of their nodes and never visit a node twice. Their amount can grow exponentially
+  /// with the size of the graph, which is why a limit is required; a limit of 0 returns no paths.
+  pub fn all_shortest_paths(&self, limit: usize) -> Vec<Vec<usize>>
+  {
+    let (Some(start), Some(end)) = (self.start(), self.end()) else { return vec![]; };
+    if limit == 0 { return vec![]; }
+    let lines = self.shortest_path_lines();
+    if lines.is_empty() { return if start == end && self.get(start).is_some() { vec![vec![start]] } else { vec![] }; }
+
+    let mut paths = vec![];
+    let mut path = vec![start];
+    // The index of the next line to try for every node on the current path
+    let mut next_lines = vec![lines.partition_point(|(from, _)| *from < start)];
+
+    while let Some(line_index) = next_lines.last_mut()
+    {
+      let current = *path.last().unwrap();
+
+      if current == end
+      {
+        paths.push(path.clone());
+        if paths.len() >= limit { break; }
+        path.pop();
+        next_lines.pop();
+        continue;
+      }
+
+      match lines.get(*line_index)
+      {
+        Some((from, to)) if *from == current =>
+        {
+          *line_index += 1;
+          // Lines with a length of 0 can form cycles, which aren't followed
+          if path.contains(to) { continue; }
+          path.push(*to);
+          next_lines.push(lines.partition_point(|(from, _)| from < to));
+        },
+        _ =>
+        {
+          path.pop();
+          next_lines.pop();
+        },
+      }
+    }
+
+    return paths;
+  }
+}
+
+// Tests
+#[path = "./tests/shortest_paths_tests.rs"]
+#[cfg(test)]
+mod shortest_paths_tests;
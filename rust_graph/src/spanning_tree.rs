@@ -0,0 +1,155 @@
+//! Minimum spanning trees with Prim's and Kruskal's algorithm.
+//!
+//! Spanning trees are defined for undirected graphs, so the direction of the lines is ignored: `a → b` and `b → a`
+//! connect the same two nodes and the shorter one of them counts. Lines from a node to itself are never part of a
+//! spanning tree.
+
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap}};
+
+use crate::DijkstraGraph;
+
+/// The algorithm used to find the minimum spanning tree
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SpanningTreeAlgorithm
+{
+  /// Grows every tree from its node with the lowest id by always adding the shortest line leaving it
+  #[default]
+  Prim,
+  /// Adds the lines from shortest to longest, skipping those that would close a cycle
+  Kruskal,
+}
+
+/// A minimum spanning tree, or a forest of them if the graph is disconnected
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct SpanningForest
+{
+  /// The lines of the forest as `(lower id, higher id, length)` in the order they were added
+  pub lines: Vec<(usize, usize, i32)>,
+  /// The sum of the lengths of all lines
+  pub total_weight: i64,
+  /// The amount of trees; every node that isn't connected to any other node is a tree of its own
+  pub trees: usize,
+}
+
+impl SpanningForest
+{
+  /// Returns true if the forest consists of a single tree, meaning that the graph is connected
+  pub fn is_tree(&self) -> bool
+  { return self.trees <= 1; }
+
+  /// Returns true if the forest contains the line between `a` and `b`, in either direction
+  pub fn contains(&self, a: usize, b: usize) -> bool
+  { return self.lines.iter().any(|(from, to, _)| (*from, *to) == (a.min(b), a.max(b))); }
+}
+
+impl DijkstraGraph
+{
+  /// Returns every pair of connected nodes once as `(lower id, higher id, length)`, sorted by the ids. If both
+  /// directions exist the shorter length is used.
+  pub fn undirected_lines(&self) -> Vec<(usize, usize, i32)>
+  {
+    let mut lengths: HashMap<(usize, usize), i32> = HashMap::new();
+
+    self.lines().iter()
+      .filter(|(from, _, _, to, _)| from != to)
+      .for_each(|(from, _, distance, to, _)|
+      {
+        let length = lengths.entry((*from.min(to), *from.max(to))).or_insert(*distance);
+        *length = (*length).min(*distance);
+      });
+
+    let mut lines: Vec<(usize, usize, i32)> = lengths.into_iter().map(|((a, b), length)| (a, b, length)).collect();
+    lines.sort_unstable();
+    return lines;
+  }
+
+  /// Finds the minimum spanning forest of the graph with the given algorithm, treating all lines as undirected
+  pub fn minimum_spanning_forest(&self, algorithm: SpanningTreeAlgorithm) -> SpanningForest
+  {
+    let lines = match algorithm
+    {
+      SpanningTreeAlgorithm::Prim => self.prim(),
+      SpanningTreeAlgorithm::Kruskal => self.kruskal(),
+    };
+
+    return SpanningForest
+    {
+      total_weight: lines.iter().map(|(_, _, length)| *length as i64).sum(),
+      // Every line of a forest joins two trees into one
+      trees: self.size() - lines.len(),
+      lines,
+    };
+  }
+
+  fn prim(&self) -> Vec<(usize, usize, i32)>
+  {
+    let mut neighbours: Vec<Vec<(usize, i32)>> = vec![vec![]; self.points().len()];
+    self.undirected_lines().iter()
+      .for_each(|(a, b, length)|
+      {
+        neighbours[*a].push((*b, *length));
+        neighbours[*b].push((*a, *length));
+      });
+
+    let mut in_tree = vec![false; self.points().len()];
+    let mut lines = vec![];
+
+    for root in (0..self.points().len()).filter(|id| self.get(*id).is_some())
+    {
+      if in_tree[root] { continue; }
+      in_tree[root] = true;
+
+      let mut frontier: BinaryHeap<Reverse<(i32, usize, usize)>> = neighbours[root].iter()
+        .map(|(to, length)| Reverse((*length, root, *to)))
+        .collect();
+
+      while let Some(Reverse((length, from, to))) = frontier.pop()
+      {
+        if in_tree[to] { continue; }
+        in_tree[to] = true;
+        lines.push((from.min(to), from.max(to), length));
+
+        neighbours[to].iter()
+          .filter(|(next, _)| !in_tree[*next])
+          .for_each(|(next, next_length)| frontier.push(Reverse((*next_length, to, *next))));
+      }
+    }
+
+    return lines;
+  }
+
+  fn kruskal(&self) -> Vec<(usize, usize, i32)>
+  {
+    let mut sorted_lines = self.undirected_lines();
+    sorted_lines.sort_by_key(|(a, b, length)| (*length, *a, *b));
+
+    // Union-find with path halving
+    let mut parents: Vec<usize> = (0..self.points().len()).collect();
+    fn find(parents: &mut [usize], mut id: usize) -> usize
+    {
+      while parents[id] != id
+      {
+        parents[id] = parents[parents[id]];
+        id = parents[id];
+      }
+      return id;
+    }
+
+    let mut lines = vec![];
+
+    for (a, b, length) in sorted_lines
+    {
+      let (root_a, root_b) = (find(&mut parents, a), find(&mut parents, b));
+      if root_a == root_b { continue; }
+      parents[root_a.max(root_b)] = root_a.min(root_b);
+      lines.push((a, b, length));
+    }
+
+    return lines;
+  }
+}
+
+// Tests
+#[path = "./tests/spanning_tree_tests.rs"]
+#[cfg(test)]
+mod spanning_tree_tests;
@@ -0,0 +1,110 @@
+use crate::{search::PathAlgorithm, DijkstraGraph};
+
+/// 0 --2--> 1 --1--> 2, 100 pixels apart from each other
+/// 0 -------5------> 2
+fn triangle() -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(100., 100.);
+  graph.append_point(200., 100.);
+  graph.append_point(300., 100.);
+  graph.add_line(0, 1, 2);
+  graph.add_line(1, 2, 1);
+  graph.add_line(0, 2, 5);
+  graph.set_start(0);
+  graph.set_end(2);
+  return graph;
+}
+
+/// A grid of 10 × 10 points that are 100 pixels apart and connected to their neighbours in both directions by lines
+/// of length 10
+fn grid() -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  for id in 0..100
+  { graph.append_point((id % 10) as f32 * 100., (id / 10) as f32 * 100.); }
+  for id in 0..100
+  {
+    if id % 10 != 9 { graph.add_line(id, id + 1, 10); graph.add_line(id + 1, id, 10); }
+    if id < 90 { graph.add_line(id, id + 10, 10); graph.add_line(id + 10, id, 10); }
+  }
+  return graph;
+}
+
+#[test]
+fn consistent_scale()
+{
+  assert_eq!(triangle().consistent_scale(), Some(0.01));
+  assert_eq!(DijkstraGraph::new().consistent_scale(), None);
+
+  // Lines that aren't positive don't pull the scale down to zero or below
+  let mut graph = triangle();
+  graph.add_line(2, 1, 0);
+  graph.add_line(1, 0, -3);
+  assert_eq!(graph.consistent_scale(), Some(0.01));
+
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(0., 0.);
+  graph.append_point(100., 0.);
+  graph.add_line(0, 1, -1);
+  assert_eq!(graph.consistent_scale(), None);
+}
+
+#[test]
+fn inadmissible_points()
+{
+  let mut graph = triangle();
+  assert_eq!(graph.inadmissible_points(0.01), Vec::<usize>::new());
+  // 1 is 100 pixels away from the end but only 1 away over the lines
+  assert_eq!(graph.inadmissible_points(0.02), vec![0, 1]);
+
+  graph.set_end(1);
+  assert_eq!(graph.inadmissible_points(0.02), Vec::<usize>::new());
+  assert_eq!(graph.inadmissible_points(1.), vec![0]);
+}
+
+#[test]
+fn a_star_expands_fewer_points()
+{
+  let mut graph = grid();
+  graph.set_start(0);
+  graph.set_end(9);
+
+  let dijkstra = graph.expanded_points(PathAlgorithm::Dijkstra).unwrap();
+  let a_star = graph.expanded_points(PathAlgorithm::AStar { scale: 0.1 }).unwrap();
+  assert!(a_star < dijkstra, "A* {} Dijkstra {}", a_star, dijkstra);
+
+  graph.set_path_algorithm(PathAlgorithm::AStar { scale: 0.1 });
+  assert_eq!(graph.find_shortest_path(), a_star);
+  assert_eq!(graph.get_path(), Some((0..10).collect()));
+}
+
+/// With a consistent heuristic A* has to find paths exactly as long as the ones found by Dijkstra's algorithm
+#[test]
+fn random_graphs_match_dijkstra()
+{
+  use rand::{rngs::StdRng, Rng, SeedableRng};
+
+  let mut rng = StdRng::seed_from_u64(10);
+
+  for _ in 0..50
+  {
+    let amount_of_points = rng.gen_range(2..40);
+    let mut graph = DijkstraGraph::new();
+    for _ in 0..amount_of_points
+    { graph.append_point(rng.gen_range(0. ..1000.), rng.gen_range(0. ..700.)); }
+    for _ in 0..amount_of_points * 3
+    { graph.add_line(rng.gen_range(0..amount_of_points), rng.gen_range(0..amount_of_points), rng.gen_range(1..=20)); }
+    graph.set_start(0);
+    graph.set_end(amount_of_points - 1);
+
+    let distance = graph.distances_from(0)[amount_of_points - 1];
+    let scale = graph.consistent_scale().unwrap_or(0.);
+    assert!(graph.inadmissible_points(scale).is_empty());
+
+    graph.set_path_algorithm(PathAlgorithm::AStar { scale });
+    graph.find_shortest_path();
+
+    assert_eq!(graph.get(amount_of_points - 1).unwrap().distance().map(|distance| distance as i64), distance);
+  }
+}
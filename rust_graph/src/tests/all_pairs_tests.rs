@@ -0,0 +1,67 @@
+use super::Distance;
+use crate::DijkstraGraph;
+
+#[test]
+fn matches_single_source_distances()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_medium_graph();
+  graph.remove_point(5);
+
+  let matrix = graph.all_pairs_shortest_paths();
+
+  for from in 0..graph.points().len()
+  {
+    let distances = graph.distances_from(from);
+    for (to, distance) in distances.iter().enumerate()
+    {
+      assert_eq!(matrix.distance(from, to), distance.map_or(Distance::Unreachable, Distance::Finite), "{} → {}", from, to);
+
+      // Following the next hops has to add up to the same distance
+      let Some(path) = matrix.path(from, to) else { continue; };
+      let length: i64 = path.iter().zip(path.iter().skip(1))
+        .map(|(a, b)| graph.get(*a).unwrap().edges().iter().find(|edge| edge.destination() == *b).unwrap().distance() as i64)
+        .sum();
+      assert_eq!(Some(length), *distance, "{} → {}", from, to);
+    }
+  }
+
+  assert!(!matrix.has_negative_cycle());
+  assert_eq!(matrix.distance(5, 5), Distance::Unreachable);
+}
+
+#[test]
+fn small_graph_path()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+
+  let matrix = graph.all_pairs_shortest_paths();
+  assert_eq!(matrix.distance(2, 4), Distance::Finite(10));
+  assert_eq!(matrix.path(2, 4), Some(vec![2, 3, 4]));
+  assert_eq!(matrix.path(4, 2), None);
+  assert_eq!(matrix.path(3, 3), Some(vec![3]));
+}
+
+#[test]
+fn negative_cycle()
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..5
+  { graph.append_point(0., 0.); }
+  graph.add_line(0, 1, 1);
+  graph.add_line(1, 2, -2);
+  graph.add_line(2, 1, 1);
+  graph.add_line(2, 3, 4);
+  graph.add_line(4, 0, -3);
+
+  let matrix = graph.all_pairs_shortest_paths();
+  assert!(matrix.has_negative_cycle());
+
+  assert_eq!(matrix.distance(0, 3), Distance::NegativeInfinity);
+  assert_eq!(matrix.distance(1, 1), Distance::NegativeInfinity);
+  assert_eq!(matrix.path(0, 3), None);
+  // These paths can't lead over the cycle
+  assert_eq!(matrix.distance(4, 0), Distance::Finite(-3));
+  assert_eq!(matrix.distance(3, 0), Distance::Unreachable);
+}
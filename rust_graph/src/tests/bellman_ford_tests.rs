@@ -0,0 +1,111 @@
+use crate::{search::PathAlgorithm, DijkstraGraph};
+
+fn graph_with_lines(amount_of_points: usize, lines: &[(usize, usize, i32)]) -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..amount_of_points
+  { graph.append_point(0., 0.); }
+  for (from, to, distance) in lines
+  { graph.add_line(*from, *to, *distance); }
+  return graph;
+}
+
+#[test]
+fn matches_dijkstra()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_medium_graph();
+
+  for start in 0..graph.points().len()
+  { assert_eq!(graph.bellman_ford(start), Ok(graph.distances_from(start)), "{}", start); }
+}
+
+/// Dijkstra's algorithm visits 1 before it finds the shorter way over 2 and never looks back
+#[test]
+fn dijkstra_fails_on_negative_lines()
+{
+  let mut graph = graph_with_lines(4, &[(0, 1, 4), (0, 2, 5), (2, 1, -3), (1, 3, 1)]);
+  graph.set_start(0);
+  graph.set_end(3);
+  assert!(graph.has_negative_lines());
+
+  graph.find_shortest_path();
+  assert_eq!(graph.get_path(), Some(vec![0, 1, 3]));
+
+  graph.set_path_algorithm(PathAlgorithm::BellmanFord);
+  graph.find_shortest_path();
+  assert_eq!(graph.get_path(), Some(vec![0, 2, 1, 3]));
+  assert_eq!(graph.get(3).unwrap().distance(), Some(3));
+  assert_eq!(graph.negative_cycle(), None);
+
+  assert_eq!(graph.distances_from(0), vec![Some(0), Some(2), Some(5), Some(3)]);
+  assert_eq!(graph.distances_to(3), vec![Some(3), Some(1), Some(-2), Some(0)]);
+}
+
+/// Lines of length zero aren't negative, every algorithm has to find the shortest path across them
+#[test]
+fn zero_length_lines()
+{
+  // 0 and 1 as well as 2 and 3 are connected by lines of length 0 in both directions
+  let mut graph = graph_with_lines(5, &[(0, 1, 0), (1, 0, 0), (1, 2, 3), (0, 2, 3), (2, 3, 0), (3, 2, 0), (3, 4, 2)]);
+  graph.set_start(0);
+  graph.set_end(4);
+  assert!(!graph.has_negative_lines());
+
+  for path_algorithm in [PathAlgorithm::Dijkstra, PathAlgorithm::AStar { scale: 0. }, PathAlgorithm::BellmanFord]
+  {
+    graph.set_path_algorithm(path_algorithm);
+    graph.find_shortest_path();
+    assert_eq!(graph.get_path(), Some(vec![0, 2, 3, 4]), "{:?}", path_algorithm);
+    assert_eq!(graph.get(4).unwrap().distance(), Some(5), "{:?}", path_algorithm);
+  }
+  assert_eq!(graph.distances_from(0), vec![Some(0), Some(0), Some(3), Some(3), Some(5)]);
+}
+
+#[test]
+fn negative_cycle()
+{
+  let mut graph = graph_with_lines(4, &[(0, 1, 1), (1, 2, -2), (2, 1, 1), (2, 3, 1)]);
+  graph.set_start(0);
+  graph.set_end(3);
+  graph.set_path_algorithm(PathAlgorithm::BellmanFord);
+
+  graph.find_shortest_path();
+  assert_eq!(graph.get_path(), None);
+  assert_eq!(graph.negative_cycle(), Some([1, 2].as_slice()));
+
+  // Any edit makes the cycle disappear until the next search
+  graph.remove_line(2, 1);
+  assert_eq!(graph.negative_cycle(), None);
+  graph.find_shortest_path();
+  assert_eq!(graph.get_path(), Some(vec![0, 1, 2, 3]));
+
+  assert_eq!(graph_with_lines(4, &[(0, 1, 1), (1, 2, -2), (2, 1, 1), (2, 3, 1)]).bellman_ford(0), Err(vec![1, 2]));
+  assert!(graph_with_lines(4, &[(0, 1, 1), (1, 2, -2), (2, 1, 1), (2, 3, 1)]).distances_from(0).iter().all(Option::is_none));
+}
+
+#[test]
+fn cycle_direction()
+{
+  let graph = graph_with_lines(5, &[(0, 2, -1), (2, 1, -1), (1, 0, -1), (1, 3, 1), (4, 0, 1)]);
+
+  assert_eq!(graph.bellman_ford(4), Err(vec![0, 2, 1]));
+  assert_eq!(graph.bellman_ford_to(3), Err(vec![0, 2, 1]));
+  // The cycle can't be reached from 3 and 3 can't reach 4
+  assert!(graph.bellman_ford(3).is_ok());
+  assert!(graph.bellman_ford_to(4).is_ok());
+
+  assert_eq!(graph_with_lines(2, &[(0, 1, 1), (1, 1, -1)]).bellman_ford(0), Err(vec![1]));
+}
+
+#[test]
+fn negative_lines_are_saved()
+{
+  let graph = graph_with_lines(2, &[(0, 1, -7)]);
+
+  let json_graph = DijkstraGraph::from_json(&graph.to_json()).unwrap();
+  assert_eq!(json_graph.get(0).unwrap().edges()[0].distance(), -7);
+
+  let dot_graph = DijkstraGraph::from_dot(&graph.to_dot()).unwrap();
+  assert_eq!(dot_graph.get(0).unwrap().edges()[0].distance(), -7);
+}
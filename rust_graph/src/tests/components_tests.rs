@@ -0,0 +1,75 @@
+use crate::DijkstraGraph;
+
+/// The cycles 0 → 1 → 2 → 0 and 3 → 4 → 3, connected by 2 → 3 and 1 → 4, and 5 on its own with a line to 0
+fn two_cycles() -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  for id in 0..6
+  { graph.add_point(id, id as f32 * 10., 0.); }
+  graph.add_line(0, 1, 1);
+  graph.add_line(1, 2, 1);
+  graph.add_line(2, 0, 1);
+  graph.add_line(3, 4, 1);
+  graph.add_line(4, 3, 1);
+  graph.add_line(2, 3, 7);
+  graph.add_line(1, 4, 5);
+  graph.add_line(5, 0, 2);
+  return graph;
+}
+
+#[test]
+fn components_are_found_in_topological_order()
+{
+  let components = two_cycles().strongly_connected_components();
+
+  assert_eq!(components.components, vec![vec![5], vec![0, 1, 2], vec![3, 4]]);
+  assert_eq!(components.component_of, vec![Some(1), Some(1), Some(1), Some(2), Some(2), Some(0)]);
+  assert!(!components.is_strongly_connected());
+}
+
+#[test]
+fn removed_points_and_empty_graphs()
+{
+  let mut graph = two_cycles();
+  graph.remove_point(5);
+  let components = graph.strongly_connected_components();
+  assert_eq!(components.len(), 2);
+  assert_eq!(components.component_of[5], None);
+
+  let components = DijkstraGraph::new().strongly_connected_components();
+  assert!(components.is_empty());
+  assert!(components.is_strongly_connected());
+}
+
+#[test]
+fn condensation()
+{
+  let graph = two_cycles();
+  let components = graph.strongly_connected_components();
+  let condensation = graph.condensation(&components);
+
+  assert_eq!(condensation.size(), 3);
+  let centre = condensation.get(1).unwrap();
+  assert_eq!((centre.x, centre.y), (10., 0.));
+
+  let mut lines: Vec<(usize, i32, usize)> = condensation.lines().iter().map(|(from, _, distance, to, _)| (*from, *distance, *to)).collect();
+  lines.sort_unstable();
+  // The shorter of the two lines from the first cycle to the second one
+  assert_eq!(lines, vec![(0, 2, 1), (1, 5, 2)]);
+  assert!(condensation.strongly_connected_components().components.iter().all(|component| component.len() == 1));
+}
+
+/// A long cycle must not overflow the stack
+#[test]
+fn long_cycle()
+{
+  let mut graph = DijkstraGraph::new();
+  for id in 0..100_000
+  {
+    graph.add_point(id, 0., 0.);
+    if id > 0 { graph.add_line(id - 1, id, 1); }
+  }
+  graph.add_line(99_999, 0, 1);
+
+  assert!(graph.strongly_connected_components().is_strongly_connected());
+}
@@ -0,0 +1,160 @@
+use super::{tokenize, DotError, Token};
+use crate::DijkstraGraph;
+
+#[test]
+fn round_trip()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_large_graph();
+  graph.remove_point(4);
+
+  let loaded = DijkstraGraph::from_dot(&graph.to_dot()).unwrap();
+
+  assert_eq!(loaded.size(), graph.size());
+  assert!(loaded.get(4).is_none());
+
+  let lines = |graph: &DijkstraGraph| graph.lines().iter()
+    .map(|(from, from_point, distance, to, _)| (*from, from_point.x, from_point.y, *distance, *to))
+    .collect::<Vec<_>>();
+  assert_eq!(lines(&loaded), lines(&graph));
+}
+
+#[test]
+fn named_nodes_and_chains()
+{
+  let dot = r#"
+    /* Reference graph */
+    strict digraph "Example" {
+      rankdir = LR;
+      node [shape=circle]
+      start [pos="10,20!"]
+      start -> a -> "node b" [weight=4] // Both edges have a length of 4
+      a -> end [label="7"];
+      "node b" -> end [label="far", color=red]
+      # Preprocessor line
+    }
+  "#;
+
+  let mut graph = DijkstraGraph::from_dot(dot).unwrap();
+
+  assert_eq!(graph.size(), 4);
+  // Nodes are numbered in the order they appear in
+  let start = graph.get(0).unwrap();
+  assert_eq!((start.x, start.y), (10., 20.));
+
+  let lines = graph.lines().iter()
+    .map(|(from, _, distance, to, _)| (*from, *to, *distance))
+    .collect::<Vec<_>>();
+  assert_eq!(lines, vec![(0, 1, 4), (1, 2, 4), (1, 3, 7), (2, 3, 1)]);
+
+  graph.set_start(0);
+  graph.set_end(3);
+  graph.find_shortest_path();
+  assert_eq!(graph.get_path(), Some(vec![0, 1, 2, 3]));
+}
+
+#[test]
+fn numbers_that_name_the_same_id()
+{
+  let graph = DijkstraGraph::from_dot(r#"digraph { 1 -> 01 [label=2]; "+1" -> 1 [label=3] }"#).unwrap();
+
+  // All three are different nodes, so they are numbered in the order they appear in
+  assert_eq!(graph.size(), 3);
+  let lines = graph.lines().iter()
+    .map(|(from, _, distance, to, _)| (*from, *to, *distance))
+    .collect::<Vec<_>>();
+  assert_eq!(lines, vec![(0, 1, 2), (2, 0, 3)]);
+}
+
+#[test]
+fn ids_ending_in_a_backslash()
+{
+  let tokens = tokenize(r#""a\\" -> "b\"c""#).unwrap();
+  let ids: Vec<&Token> = tokens.iter().map(|(token, _)| token).collect();
+  assert_eq!(ids, vec![&Token::Id(String::from(r"a\\")), &Token::Arrow, &Token::Id(String::from(r#"b"c"#))]);
+
+  let graph = DijkstraGraph::from_dot(r#"digraph { "a\\" -> "b" [label=4]; "b" -> "a\\" }"#).unwrap();
+  assert_eq!(graph.size(), 2);
+  let lines = graph.lines().iter()
+    .map(|(from, _, distance, to, _)| (*from, *to, *distance))
+    .collect::<Vec<_>>();
+  assert_eq!(lines, vec![(0, 1, 4), (1, 0, 1)]);
+}
+
+#[test]
+fn many_nodes()
+{
+  let chain = (0..20_000).map(|id| format!("n{}", id)).collect::<Vec<String>>().join(" -> ");
+  let graph = DijkstraGraph::from_dot(&format!("digraph {{ {} }}", chain)).unwrap();
+
+  assert_eq!(graph.size(), 20_000);
+  assert_eq!(graph.lines().len(), 19_999);
+  assert_eq!(graph.get(19_999).unwrap().edges.len(), 0);
+  assert_eq!(graph.get(19_998).unwrap().edges[0].destination, 19_999);
+}
+
+#[test]
+fn nodes_without_position_are_laid_out()
+{
+  let graph = DijkstraGraph::from_dot("digraph { 3 -> 7; 7 -> 12; 12 -> 3 }").unwrap();
+
+  // Numeric names are used as ids
+  assert_eq!(graph.size(), 3);
+  let points = [3, 7, 12].map(|id| graph.get(id).unwrap());
+
+  // All nodes land on distinct, finite positions
+  for (index, point) in points.iter().enumerate()
+  {
+    assert!(point.x.is_finite() && point.y.is_finite());
+    for other in points.iter().skip(index + 1)
+    { assert!((point.x - other.x).abs() + (point.y - other.y).abs() > 1.); }
+  }
+}
+
+#[test]
+fn undirected_graphs()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.set_undirected(true);
+  for _ in 0..3
+  { graph.append_point(0., 0.); }
+  graph.add_line(0, 1, 4);
+  graph.add_line(2, 1, -2);
+
+  let dot = graph.to_dot();
+  assert!(dot.starts_with("graph {"));
+  assert!(dot.contains("0 -- 1 [label=\"4\", weight=4];"));
+  assert!(dot.contains("1 -- 2 [label=\"-2\"];"));
+  assert_eq!(dot.matches("--").count(), 2);
+
+  let loaded = DijkstraGraph::from_dot(&dot).unwrap();
+  assert!(loaded.is_undirected());
+  assert_eq!(loaded.lines().len(), 4);
+
+  let loaded = DijkstraGraph::from_dot("strict graph { a -- b -- c [weight=3] }").unwrap();
+  assert_eq!(loaded.get(2).unwrap().edges()[0].distance(), 3);
+}
+
+#[test]
+fn malformed_files()
+{
+  let line_of = |dot: &str| match DijkstraGraph::from_dot(dot)
+  {
+    Err(DotError::Syntax { line, .. }) => line,
+    _ => panic!("{} should not have been accepted", dot),
+  };
+
+  assert_eq!(line_of("graph {\n a -> b }"), 2);
+  assert_eq!(line_of("digraph { a -- b }"), 1);
+  assert_eq!(line_of("tree { a -- b }"), 1);
+  assert_eq!(line_of("digraph {\n a -> b\n"), 2);
+  assert_eq!(line_of("digraph {\n a -> b [weight=heavy]\n}"), 2);
+  assert_eq!(line_of("digraph {\n\n a [pos=\"1\"]\n}"), 3);
+  assert_eq!(line_of("digraph { subgraph cluster { a } }"), 1);
+  assert_eq!(line_of("digraph { a:n -> b }"), 1);
+  assert_eq!(line_of("digraph { a -> b [label=\"4] }"), 1);
+  assert_eq!(line_of("digraph { a -> b } c"), 1);
+  assert_eq!(line_of("digraph {\n /* a -> b\n }"), 2);
+
+  assert!(matches!(DijkstraGraph::load_dot("this/file/does/not/exist.dot"), Err(DotError::Io(_))));
+}
@@ -0,0 +1,93 @@
+use crate::DijkstraGraph;
+
+/// The classic example where a bad choice of paths needs to take back flow: 0 → 1 → 3 and 0 → 2 → 3 with the line
+/// 1 → 2 in between
+fn network() -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..4
+  { graph.append_point(0., 0.); }
+  graph.add_line(0, 1, 3);
+  graph.add_line(0, 2, 2);
+  graph.add_line(1, 2, 5);
+  graph.add_line(1, 3, 2);
+  graph.add_line(2, 3, 3);
+  return graph;
+}
+
+#[test]
+fn maximum_flow()
+{
+  let flow = network().max_flow(0, 3).unwrap();
+
+  assert_eq!(flow.value, 5);
+  let flows: Vec<(usize, usize, i64, i64)> = flow.lines.iter().map(|line| (line.from, line.to, line.flow, line.capacity)).collect();
+  assert_eq!(flows, vec![(0, 1, 3, 3), (0, 2, 2, 2), (1, 2, 1, 5), (1, 3, 2, 2), (2, 3, 3, 3)]);
+
+  // The shortest paths are used first
+  assert_eq!(flow.augmenting_paths[0].path, vec![0, 1, 3]);
+  assert_eq!(flow.augmenting_paths[1].path, vec![0, 2, 3]);
+  assert_eq!(flow.augmenting_paths.len(), 3);
+  assert_eq!(flow.flows_after(3), flow.lines.iter().map(|line| line.flow).collect::<Vec<i64>>());
+  assert_eq!(flow.flows_after(0), vec![0; 5]);
+}
+
+#[test]
+fn minimum_cut()
+{
+  let flow = network().max_flow(0, 3).unwrap();
+
+  assert_eq!(flow.min_cut(), vec![(0, 1), (0, 2)]);
+  assert_eq!(flow.source_side, vec![true, false, false, false]);
+}
+
+#[test]
+fn cut_capacity_is_flow_value()
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..4
+  { graph.append_point(0., 0.); }
+  graph.add_line(0, 1, 1);
+  graph.add_line(1, 2, 1);
+  graph.add_line(2, 3, 1);
+  graph.add_line(0, 2, 1);
+  graph.add_line(1, 3, 1);
+
+  let flow = graph.max_flow(0, 3).unwrap();
+  assert_eq!(flow.value, 2);
+  let cut_capacity: i64 = flow.lines.iter()
+    .filter(|line| flow.min_cut().contains(&(line.from, line.to)))
+    .map(|line| line.capacity)
+    .sum();
+  assert_eq!(cut_capacity, 2);
+}
+
+#[test]
+fn invalid_terminals_and_negative_lines()
+{
+  let mut graph = network();
+  assert!(graph.max_flow(0, 0).is_none());
+  assert!(graph.max_flow(0, 9).is_none());
+
+  graph.add_line(0, 3, -4);
+  let flow = graph.max_flow(0, 3).unwrap();
+  assert_eq!(flow.value, 5);
+  assert!(flow.lines.iter().any(|line| (line.from, line.to, line.capacity, line.flow) == (0, 3, 0, 0)));
+}
+
+#[test]
+fn flow_is_taken_back()
+{
+  // 0 → 1 → 2 → 5 is the shortest path and blocks both detours, the second path has to go backwards over 1 → 2
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..8
+  { graph.append_point(0., 0.); }
+  for (from, to) in [(0, 1), (1, 2), (2, 5), (0, 3), (3, 6), (6, 2), (1, 4), (4, 7), (7, 5)]
+  { graph.add_line(from, to, 1); }
+
+  let flow = graph.max_flow(0, 5).unwrap();
+  assert_eq!(flow.value, 2);
+  assert_eq!(flow.augmenting_paths[1].path, vec![0, 3, 6, 2, 1, 4, 7, 5]);
+  assert!(flow.augmenting_paths[1].changes.contains(&(2, -1)));
+  assert_eq!((flow.lines[2].from, flow.lines[2].to, flow.lines[2].flow), (1, 2, 0));
+}
@@ -0,0 +1,90 @@
+use super::*;
+
+#[test]
+fn circle_tests()
+{
+  assert!(is_point_in_circle(240., 78., 233., 73., 13.));
+  assert!(!is_point_in_circle(752., 251., 725., 270., 13.));
+  assert!(!is_point_in_circle(81., 24., 40., 40., 20.));
+  assert!(is_point_in_circle(25., 29., 40., 40., 20.));
+  assert!(!is_point_in_circle(189., 176., 143., 136., 57.));
+  assert!(is_point_in_circle(192., 87., 143., 136., 75.));
+  assert!(!is_point_in_circle(177., 243., 153., 250., 4.));
+  assert!(is_point_in_circle(201., 251., 200., 250., 4.));
+  assert!(!is_point_in_circle(1020., 315., 873., 310., 48.));
+  assert!(is_point_in_circle(606., 411., 615., 404., 22.));
+}
+
+#[test]
+fn rectangle_tests()
+{
+  assert!(!is_point_in_rectangle(747., 313., 600., 357., 100., 100.));
+  assert!(!is_point_in_rectangle(433., 226., 448., 170., 38., 100.));
+  assert!(is_point_in_rectangle(382., 274., 213., 165., 324., 238.));
+  assert!(!is_point_in_rectangle(664., 180., 563., 211., 62., 67.));
+  assert!(!is_point_in_rectangle(601., 182., 587., 389., 31., 92.));
+  assert!(!is_point_in_rectangle(821., 85., 509., 53., 276., 67.));
+  assert!(!is_point_in_rectangle(229., 328., 77., 139., 143., 181.));
+  assert!(!is_point_in_rectangle(450., 618., 383., 384., 191., 134.));
+  assert!(!is_point_in_rectangle(729., 685., 813., 418., 96., 248.));
+  assert!(!is_point_in_rectangle(305., 617., 355., 588., 96., 86.));
+  assert!(is_point_in_rectangle(637., 297., 419., 68., 248., 248.));
+  assert!(is_point_in_rectangle(803., 429., 781., 404., 210., 134.));
+  assert!(is_point_in_rectangle(135., 88., 104., 55., 162., 305.));
+}
+
+fn is_close(a: (f32, f32), b: (f32, f32)) -> bool
+{ return (a.0 - b.0).abs() < 0.001 && (a.1 - b.1).abs() < 0.001; }
+
+#[test]
+fn curves()
+{
+  let (start, end) = ((0., 0.), (100., 0.));
+  let control = curve_control_point(start, end);
+  // Bends to the right of the direction, which is downwards here, and the opposite direction bends the other way
+  assert!(is_close(control, (50., 15.)));
+  assert!(is_close(curve_control_point(end, start), (50., -15.)));
+
+  assert_eq!(quadratic_bezier(start, control, end, 0.), start);
+  assert_eq!(quadratic_bezier(start, control, end, 1.), end);
+  assert!(is_close(quadratic_bezier(start, control, end, 0.5), (50., 7.5)));
+  assert!(is_close(quadratic_bezier_tangent(start, control, end, 0.5), (100., 0.)));
+
+  let exit = quadratic_bezier_exit(start, control, end, 10.);
+  let point = quadratic_bezier(start, control, end, exit);
+  assert!(((point.0 - end.0).hypot(point.1 - end.1) - 10.).abs() < 0.01);
+  assert_eq!(quadratic_bezier_exit(start, control, end, 200.), 0.);
+}
+
+#[test]
+fn distances()
+{
+  assert_eq!(distance_to_segment((5., 5.), (0., 0.), (10., 0.)), 5.);
+  assert_eq!(distance_to_segment((-3., 4.), (0., 0.), (10., 0.)), 5.);
+  assert_eq!(distance_to_segment((3., 4.), (0., 0.), (0., 0.)), 5.);
+
+  let (start, end) = ((0., 0.), (100., 0.));
+  let control = curve_control_point(start, end);
+  assert!(distance_to_quadratic_bezier((50., 7.5), start, control, end) < 0.1);
+  assert!(distance_to_quadratic_bezier((50., 0.), start, control, end) > 7.);
+}
+
+#[test]
+fn self_loops()
+{
+  let ((center_x, center_y), loop_radius) = self_loop_circle(100., 100., 10.);
+  assert_eq!((center_x, center_y, loop_radius), (100., 84., 10.));
+
+  let (leaving, entering) = self_loop_ends(100., 100., 10.);
+  for (x, y) in [leaving, entering]
+  {
+    assert!(((x - 100.).hypot(y - 100.) - 10.).abs() < 0.001);
+    assert!(((x - center_x).hypot(y - center_y) - loop_radius).abs() < 0.001);
+  }
+  assert!(leaving.0 < entering.0);
+
+  assert!(is_point_on_self_loop(100., 73., 100., 100., 10., 2.));
+  assert!(!is_point_on_self_loop(100., 84., 100., 100., 10., 2.));
+  // The part of the circle inside the node isn't part of the loop
+  assert!(!is_point_on_self_loop(100., 94., 100., 100., 10., 2.));
+}
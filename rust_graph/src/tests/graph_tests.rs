@@ -1,4 +1,5 @@
 use super::DijkstraGraph;
+use crate::PathAlgorithm;
 use rand::*;
 
 const RADIUS: f32 = 13.;
@@ -9,11 +10,16 @@ fn random_x(radius: f32) -> f32
 fn random_y(radius: f32) -> f32
 { return thread_rng().gen_range(radius..(720. - radius)); }
 
-fn generate_random_points_graph(amount_of_points: u8) -> DijkstraGraph
+fn add_random_points(graph: &mut DijkstraGraph, amount_of_points: usize)
 {
-  let mut graph = DijkstraGraph::new();
   for _ in 1..=amount_of_points
   { graph.append_point(random_x(RADIUS), random_y(RADIUS)); }
+}
+
+fn generate_random_points_graph(amount_of_points: usize) -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  add_random_points(&mut graph, amount_of_points);
   return graph;
 }
 
@@ -31,13 +37,51 @@ fn add_many_points()
   assert_eq!(graph.size(), 50);
 }
 
+#[test]
+fn more_than_a_hundred_points()
+{
+  let graph = generate_random_points_graph(1_000);
+  assert_eq!(graph.size(), 1_000);
+}
+
 #[test]
 fn max_amount_of_points()
 {
-  // Creating graph and "adding" 1_000 points to it
-  let graph = generate_random_points_graph(255);
+  // Creating a graph with a limit of 100 points and "adding" 255 points to it
+  let mut graph = DijkstraGraph::new();
+  graph.set_max_points(Some(100));
+  add_random_points(&mut graph, 255);
   // The graph should still only have 100 points
   assert_eq!(graph.size(), 100);
+  assert!(graph.is_full());
+
+  // Raising the limit allows more points to be added
+  graph.set_max_points(Some(150));
+  add_random_points(&mut graph, 255);
+  assert_eq!(graph.size(), 150);
+
+  // Removing the limit altogether
+  graph.set_max_points(None);
+  add_random_points(&mut graph, 255);
+  assert_eq!(graph.size(), 405);
+}
+
+#[test]
+fn ids_stay_stable()
+{
+  let mut graph = generate_random_points_graph(5);
+  graph.add_point(250, 100., 100.);
+  assert_eq!(graph.size(), 6);
+  assert!(graph.get(250).is_some());
+  assert!(graph.get(249).is_none());
+
+  // Removing a point leaves a gap which is filled by the next appended point
+  graph.remove_point(2);
+  assert!(graph.get(2).is_none());
+  assert!(graph.get(3).is_some());
+  graph.append_point(200., 200.);
+  assert!(graph.get(2).is_some());
+  assert_eq!(graph.size(), 6);
 }
 
 #[test]
@@ -51,6 +95,54 @@ fn remove_points()
   assert_eq!(graph.size(), 5);
 }
 
+#[test]
+fn bounding_box()
+{
+  let mut graph = DijkstraGraph::new();
+  assert_eq!(graph.bounding_box(), None);
+
+  graph.append_point(100., 50.);
+  assert_eq!(graph.bounding_box(), Some(((100., 50.), (100., 50.))));
+  graph.append_point(-20., 300.);
+  graph.append_point(40., 10.);
+  assert_eq!(graph.bounding_box(), Some(((-20., 10.), (100., 300.))));
+
+  // Removed points don't count
+  graph.remove_point(1);
+  assert_eq!(graph.bounding_box(), Some(((40., 10.), (100., 50.))));
+}
+
+#[test]
+fn points_in_rectangle()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(0., 0.);
+  graph.append_point(50., 50.);
+  graph.append_point(100., 20.);
+  graph.append_point(200., 200.);
+
+  assert_eq!(graph.points_in_rectangle((-10., -10.), (100., 60.)), vec![0, 1, 2]);
+  // The corners can be given in any order
+  assert_eq!(graph.points_in_rectangle((250., 60.), (40., 10.)), vec![1, 2]);
+  assert!(graph.points_in_rectangle((300., 300.), (400., 400.)).is_empty());
+
+  graph.remove_point(1);
+  assert_eq!(graph.points_in_rectangle((-10., -10.), (100., 60.)), vec![0, 2]);
+}
+
+#[test]
+fn removed_points_take_their_lines_along()
+{
+  let mut graph = generate_random_points_graph(3);
+  graph.add_line(0, 1, 1);
+  graph.add_line(1, 2, 1);
+  graph.remove_point(1);
+
+  // The next point with the same id doesn't inherit the line from 0
+  graph.append_point(100., 100.);
+  assert!(graph.lines().is_empty());
+}
+
 #[test]
 fn shortest_path_small()
 {
@@ -405,3 +497,116 @@ fn cyclical_valid_path()
     None => panic!("A path should have been found"),
   }
 }
+
+#[test]
+fn revision_counts_changes()
+{
+  let mut graph = DijkstraGraph::new();
+  let revision = graph.revision();
+
+  graph.append_point(0., 0.);
+  graph.append_point(0., 0.);
+  graph.add_line(0, 1, 5);
+  assert_eq!(graph.revision(), revision + 3);
+
+  // Searching doesn't change the graph itself
+  graph.set_start(0);
+  graph.set_end(1);
+  let revision = graph.revision();
+  graph.find_shortest_path();
+  assert_eq!(graph.revision(), revision);
+
+  graph.clear();
+  assert!(graph.revision() > revision);
+}
+
+#[test]
+fn undirected_lines_share_their_length()
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..3
+  { graph.append_point(0., 0.); }
+  graph.add_line(0, 1, 5);
+  graph.add_line(1, 0, 3);
+  graph.add_line(1, 2, 4);
+
+  graph.set_undirected(true);
+  // The shorter direction wins, missing directions are added
+  assert_eq!(graph.undirected_lines(), vec![(0, 1, 3), (1, 2, 4)]);
+  assert_eq!(graph.lines().len(), 4);
+
+  graph.add_line(2, 1, 7);
+  assert_eq!(graph.get(1).unwrap().edges().iter().find(|edge| edge.destination() == 2).unwrap().distance(), 7);
+
+  graph.remove_line(1, 0);
+  assert!(graph.get(0).unwrap().edges().is_empty());
+
+  graph.set_start(0);
+  graph.set_end(2);
+  graph.add_line(0, 1, 1);
+  graph.find_shortest_path();
+  assert_eq!(graph.get_path(), Some(vec![0, 1, 2]));
+  graph.set_start(2);
+  graph.set_end(0);
+  graph.find_shortest_path();
+  assert_eq!(graph.get_path(), Some(vec![2, 1, 0]));
+
+  // Both directions stay as independent lines
+  graph.set_undirected(false);
+  graph.remove_line(1, 0);
+  assert_eq!(graph.lines().len(), 3);
+}
+
+#[test]
+fn hovered_lines_follow_their_curve()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(0., 0.);
+  graph.append_point(100., 0.);
+  graph.add_line(0, 1, 1);
+
+  assert!(!graph.is_curved(0, 1));
+  assert_eq!(graph.find_hovered_line(50., 2., RADIUS, 5.), Some((0, 1)));
+  assert_eq!(graph.find_hovered_line(50., 20., RADIUS, 5.), None);
+
+  graph.add_line(1, 0, 1);
+  assert!(graph.is_curved(0, 1) && graph.is_curved(1, 0));
+  assert_eq!(graph.find_hovered_line(50., 7., RADIUS, 3.), Some((0, 1)));
+  assert_eq!(graph.find_hovered_line(50., -7., RADIUS, 3.), Some((1, 0)));
+  assert_eq!(graph.find_hovered_line(50., 0., RADIUS, 3.), None);
+
+  graph.set_undirected(true);
+  assert!(!graph.is_curved(0, 1));
+}
+
+#[test]
+fn self_loops()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(100., 100.);
+  graph.append_point(200., 100.);
+  graph.add_line(0, 0, 2);
+  graph.add_line(0, 1, 3);
+
+  assert!(graph.line_curve(0, 0).is_none());
+  assert!(!graph.is_curved(0, 0));
+  // The top of the loop, which is 1.6 radii above the point and has a radius of 1 radius
+  assert_eq!(graph.find_hovered_line(100., 100. - RADIUS * 2.6, RADIUS, 3.), Some((0, 0)));
+  assert_eq!(graph.find_hovered_line(100., 95., RADIUS, 3.), None);
+
+  // Loops never make a path shorter, unless they are negative
+  graph.set_start(0);
+  graph.set_end(1);
+  graph.find_shortest_path();
+  assert_eq!(graph.get_path(), Some(vec![0, 1]));
+  assert_eq!(graph.all_shortest_paths(10), vec![vec![0, 1]]);
+
+  graph.add_line(0, 0, 0);
+  assert_eq!(graph.all_shortest_paths(10), vec![vec![0, 1]]);
+
+  graph.add_line(0, 0, -1);
+  graph.set_path_algorithm(PathAlgorithm::BellmanFord);
+  graph.find_shortest_path();
+  assert_eq!(graph.negative_cycle(), Some(&[0][..]));
+  assert!(graph.all_pairs_shortest_paths().has_negative_cycle());
+}
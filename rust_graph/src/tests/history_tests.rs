@@ -0,0 +1,336 @@
+use super::{Edit, EditHistory, MAX_EDITS};
+use crate::DijkstraGraph;
+
+/// Everything undo has to restore: the positions of the nodes, their lines in order, the start and the end
+type State = (Vec<Option<(f32, f32)>>, Vec<(usize, i32, usize)>, Option<usize>, Option<usize>);
+
+fn state(graph: &DijkstraGraph) -> State
+{
+  let points = graph.points().iter().map(|point| point.as_ref().map(|point| (point.x, point.y))).collect();
+  let lines = graph.lines().iter().map(|(from, _, distance, to, _)| (*from, *distance, *to)).collect();
+  return (points, lines, graph.start(), graph.end());
+}
+
+fn triangle(history: &mut EditHistory) -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  history.append_point(&mut graph, 0., 0.);
+  history.append_point(&mut graph, 100., 0.);
+  history.append_point(&mut graph, 0., 100.);
+  history.add_line(&mut graph, 0, 1, 4);
+  history.add_line(&mut graph, 1, 2, 3);
+  history.add_line(&mut graph, 2, 0, 5);
+  history.add_line(&mut graph, 0, 2, 9);
+  return graph;
+}
+
+#[test]
+fn undo_everything()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+
+  while history.undo(&mut graph) {}
+  assert_eq!(graph.size(), 0);
+  assert!(graph.lines().is_empty());
+
+  let mut expected = EditHistory::new();
+  let expected = triangle(&mut expected);
+  while history.redo(&mut graph) {}
+  assert_eq!(state(&graph), state(&expected));
+}
+
+#[test]
+fn removed_points_come_back_with_their_lines()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+  history.add_line(&mut graph, 0, 0, 2);
+  let before = state(&graph);
+
+  history.remove_point(&mut graph, 0);
+  assert_eq!(graph.lines().len(), 1);
+  assert_eq!(history.next_undo().unwrap().describe(), "remove a point");
+
+  history.undo(&mut graph);
+  // The lines of the other points keep their order
+  assert_eq!(state(&graph), before);
+
+  history.redo(&mut graph);
+  assert!(graph.get(0).is_none());
+  assert_eq!(graph.lines().len(), 1);
+}
+
+#[test]
+fn lines()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+  let before = state(&graph);
+
+  history.add_line(&mut graph, 0, 1, 7);
+  assert_eq!(history.next_undo().unwrap().describe(), "change the length of a line");
+  history.remove_line(&mut graph, 1, 2);
+  assert_eq!(history.next_undo().unwrap().describe(), "remove a line");
+  // Removing a line that doesn't exist isn't an edit
+  history.remove_line(&mut graph, 1, 0);
+  assert_eq!(history.next_undo().unwrap().describe(), "remove a line");
+
+  history.undo(&mut graph);
+  history.undo(&mut graph);
+  assert_eq!(state(&graph), before);
+}
+
+#[test]
+fn undirected_lines_change_together()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+  history.replace(&mut graph, |graph| graph.set_undirected(true));
+  assert!(graph.is_undirected());
+  let before = state(&graph);
+
+  history.add_line(&mut graph, 1, 0, 2);
+  history.remove_line(&mut graph, 2, 1);
+  assert_eq!(graph.get(0).unwrap().edges().iter().find(|edge| edge.destination() == 1).unwrap().distance(), 2);
+
+  history.undo(&mut graph);
+  history.undo(&mut graph);
+  assert_eq!(state(&graph), before);
+
+  history.undo(&mut graph);
+  assert!(!graph.is_undirected());
+  assert_eq!(graph.lines().len(), 4);
+}
+
+#[test]
+fn moves()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+
+  // Putting a point back where it has been isn't an edit
+  history.start_move(&graph, &[1]);
+  history.end_move(&graph);
+  assert_eq!(history.next_undo().unwrap().describe(), "add a line");
+
+  history.start_move(&graph, &[1]);
+  graph.get_mut(1).unwrap().x = 300.;
+  history.end_move(&graph);
+  assert_eq!(history.next_undo().unwrap().describe(), "move a point");
+
+  history.undo(&mut graph);
+  assert_eq!(graph.get(1).unwrap().x, 100.);
+  history.redo(&mut graph);
+  assert_eq!(graph.get(1).unwrap().x, 300.);
+}
+
+#[test]
+fn group_moves()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+  let before = state(&graph);
+
+  // Only the points that have actually moved are recorded
+  history.start_move(&graph, &[0, 1, 2]);
+  graph.get_mut(0).unwrap().y += 40.;
+  graph.get_mut(2).unwrap().y += 40.;
+  history.end_move(&graph);
+  assert_eq!(history.next_undo().unwrap().describe(), "move 2 points");
+  let after = state(&graph);
+
+  history.undo(&mut graph);
+  assert_eq!(state(&graph), before);
+  history.redo(&mut graph);
+  assert_eq!(state(&graph), after);
+}
+
+#[test]
+fn layouts()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+  graph.set_start(0);
+  graph.set_end(2);
+  graph.find_shortest_path();
+  let (before, revision) = (state(&graph), graph.revision());
+
+  // A layout only moves the points, so the path and everything computed from the graph stay valid
+  history.set_positions(&mut graph, &[Some((0., 0.)), Some((50., 50.)), None]);
+  assert_eq!(history.next_undo().unwrap().describe(), "move a point");
+  assert_eq!((graph.get(1).unwrap().x, graph.get(1).unwrap().y), (50., 50.));
+  assert_eq!(graph.revision(), revision);
+  assert!(graph.get_path().is_some());
+
+  history.undo(&mut graph);
+  assert_eq!(state(&graph), before);
+  assert_eq!(graph.revision(), revision);
+}
+
+#[test]
+fn group_removal()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+  let before = state(&graph);
+  let edits = history.next_undo().map(|edit| edit.describe());
+
+  // Removing nothing isn't an edit
+  history.remove_points(&mut graph, &[]);
+  assert_eq!(history.next_undo().map(|edit| edit.describe()), edits);
+
+  history.remove_points(&mut graph, &[0, 1]);
+  assert_eq!(history.next_undo().unwrap().describe(), "remove 2 points");
+  assert_eq!(graph.points().iter().flatten().count(), 1);
+  assert!(graph.lines().is_empty());
+  let after = state(&graph);
+
+  // A single undo brings back both points with all their lines
+  history.undo(&mut graph);
+  assert_eq!(state(&graph), before);
+  history.redo(&mut graph);
+  assert_eq!(state(&graph), after);
+}
+
+#[test]
+fn start_and_end()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+
+  history.set_start(&mut graph, Some(0));
+  history.set_end(&mut graph, Some(2));
+  history.set_end(&mut graph, None);
+  graph.find_shortest_path();
+
+  history.undo(&mut graph);
+  assert_eq!(graph.end(), Some(2));
+  history.undo(&mut graph);
+  assert_eq!(graph.end(), None);
+  history.undo(&mut graph);
+  assert_eq!(graph.start(), None);
+}
+
+#[test]
+fn start_and_end_together()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+  history.set_start(&mut graph, Some(1));
+
+  history.set_start_and_end(&mut graph, Some(0), Some(2));
+  assert_eq!((graph.start(), graph.end()), (Some(0), Some(2)));
+  assert_eq!(history.next_undo().unwrap().describe(), "change the start and the end");
+
+  // A single undo takes back both
+  history.undo(&mut graph);
+  assert_eq!((graph.start(), graph.end()), (Some(1), None));
+
+  // Only what has actually changed is recorded
+  history.redo(&mut graph);
+  history.set_start_and_end(&mut graph, Some(0), Some(1));
+  assert_eq!(history.next_undo().unwrap().describe(), "change the end");
+}
+
+#[test]
+fn empty_edits_can_be_described()
+{
+  let mut history = EditHistory::new();
+
+  history.record(Edit::SetLines(vec![]));
+  assert_eq!(history.next_undo().unwrap().describe(), "change no lines");
+  history.record(Edit::Batch(vec![]));
+  assert_eq!(history.next_undo().unwrap().describe(), "make 0 edits");
+}
+
+#[test]
+fn clear()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+  graph.set_max_points(Some(10));
+  let before = state(&graph);
+
+  history.replace(&mut graph, |graph| graph.clear());
+  assert_eq!(graph.size(), 0);
+  // Clearing an empty graph isn't an edit
+  history.replace(&mut graph, |graph| graph.clear());
+  assert_eq!(history.next_undo().unwrap().describe(), "change the whole graph");
+
+  let revision = graph.revision();
+  history.undo(&mut graph);
+  assert_eq!(state(&graph), before);
+  assert!(graph.revision() > revision);
+  assert_eq!(graph.max_points(), Some(10));
+
+  history.redo(&mut graph);
+  assert_eq!(graph.size(), 0);
+}
+
+#[test]
+fn undirected()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+
+  history.replace(&mut graph, |graph| graph.set_undirected(true));
+  assert!(graph.is_undirected());
+
+  history.undo(&mut graph);
+  assert!(!graph.is_undirected());
+  history.redo(&mut graph);
+  assert!(graph.is_undirected());
+}
+
+#[test]
+fn replaced_graphs_count_on()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+  let revision = graph.revision();
+
+  // A new graph starts counting from the beginning
+  history.replace(&mut graph, |graph| *graph = DijkstraGraph::new());
+  assert!(graph.revision() > revision);
+}
+
+#[test]
+fn new_edits_forget_undone_ones()
+{
+  let mut history = EditHistory::new();
+  let mut graph = triangle(&mut history);
+
+  history.undo(&mut graph);
+  assert!(history.next_redo().is_some());
+  history.append_point(&mut graph, 50., 50.);
+  assert!(history.next_redo().is_none());
+  assert!(!history.redo(&mut graph));
+}
+
+#[test]
+fn oldest_edits_are_forgotten()
+{
+  let mut history = EditHistory::new();
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..MAX_EDITS + 5
+  { history.append_point(&mut graph, 0., 0.); }
+
+  let mut undone = 0;
+  while history.undo(&mut graph) { undone += 1; }
+  assert_eq!(undone, MAX_EDITS);
+  assert_eq!(graph.size(), 5);
+}
+
+#[test]
+fn full_graphs_record_nothing()
+{
+  let mut history = EditHistory::new();
+  let mut graph = DijkstraGraph::new();
+  graph.set_max_points(Some(1));
+  history.append_point(&mut graph, 0., 0.);
+  history.append_point(&mut graph, 0., 0.);
+
+  assert!(history.undo(&mut graph));
+  assert!(!history.undo(&mut graph));
+}
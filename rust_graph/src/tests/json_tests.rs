@@ -0,0 +1,103 @@
+use super::JsonError;
+use crate::DijkstraGraph;
+
+#[test]
+fn round_trip()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_medium_graph();
+  graph.remove_point(5);
+  graph.set_start(3);
+  graph.set_end(1);
+
+  let loaded = DijkstraGraph::from_json(&graph.to_json()).unwrap();
+
+  assert_eq!(loaded.size(), graph.size());
+  assert!(loaded.get(5).is_none());
+  assert_eq!(loaded.start(), Some(3));
+  assert_eq!(loaded.end(), Some(1));
+
+  let lines = |graph: &DijkstraGraph| graph.lines().iter()
+    .map(|(from, from_point, distance, to, _)| (*from, from_point.x, from_point.y, *distance, *to))
+    .collect::<Vec<_>>();
+  assert_eq!(lines(&loaded), lines(&graph));
+}
+
+#[test]
+fn undirected_round_trip()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+  graph.set_undirected(true);
+
+  let json = graph.to_json();
+  assert!(json.contains("\"undirected\": true"));
+  let loaded = DijkstraGraph::from_json(&json).unwrap();
+
+  assert!(loaded.is_undirected());
+  assert_eq!(loaded.lines().len(), graph.lines().len());
+  assert_eq!(loaded.undirected_lines(), graph.undirected_lines());
+
+  // Older files have no setting and are directed
+  let json = r#"{ "version": 1, "points": [{ "id": 0, "x": 0, "y": 0 }, { "id": 1, "x": 0, "y": 0 }], "lines": [{ "from": 0, "to": 1, "distance": 1 }] }"#;
+  let loaded = DijkstraGraph::from_json(json).unwrap();
+  assert!(!loaded.is_undirected());
+  assert_eq!(loaded.lines().len(), 1);
+}
+
+#[test]
+fn save_and_load_file()
+{
+  let path = std::env::temp_dir().join(format!("rust_graph_json_test_{}.json", std::process::id()));
+
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+  graph.save(&path).unwrap();
+
+  let mut loaded = DijkstraGraph::load(&path).unwrap();
+  std::fs::remove_file(&path).unwrap();
+
+  loaded.set_start(2);
+  loaded.set_end(4);
+  loaded.find_shortest_path();
+  assert_eq!(loaded.get_path(), Some(vec![2, 3, 4]));
+}
+
+#[test]
+fn missing_file()
+{ assert!(matches!(DijkstraGraph::load("this/file/does/not/exist.json"), Err(JsonError::Io(_)))); }
+
+#[test]
+fn malformed_files()
+{
+  assert!(matches!(DijkstraGraph::from_json("{ \"version\": 1, "), Err(JsonError::Syntax(_))));
+  assert!(matches!(DijkstraGraph::from_json("{ \"version\": 1 }"), Err(JsonError::Syntax(_))));
+  assert!(matches!(
+    DijkstraGraph::from_json(r#"{ "version": 1, "points": [{ "id": 0, "x": 1.0, "y": 1.0 }], "lines": [{ "from": 0, "to": 0, "distance": 2.5 }] }"#),
+    Err(JsonError::Syntax(_))
+  ));
+  assert!(matches!(
+    DijkstraGraph::from_json(r#"{ "version": 99, "points": [], "lines": [] }"#),
+    Err(JsonError::UnsupportedVersion(99))
+  ));
+  assert!(matches!(
+    DijkstraGraph::from_json(r#"{ "version": 0, "points": [], "lines": [] }"#),
+    Err(JsonError::UnsupportedVersion(0))
+  ));
+  assert!(matches!(
+    DijkstraGraph::from_json(r#"{ "version": 1, "points": [{ "id": 2, "x": 1.0, "y": 1.0 }, { "id": 2, "x": 5.0, "y": 1.0 }], "lines": [] }"#),
+    Err(JsonError::DuplicatePoint(2))
+  ));
+  assert!(matches!(
+    DijkstraGraph::from_json(r#"{ "version": 1, "points": [{ "id": 99999999999, "x": 1.0, "y": 1.0 }], "lines": [] }"#),
+    Err(JsonError::IdTooLarge(99999999999))
+  ));
+  assert!(matches!(
+    DijkstraGraph::from_json(r#"{ "version": 1, "points": [{ "id": 0, "x": 1.0, "y": 1.0 }], "lines": [{ "from": 0, "to": 7, "distance": 3 }] }"#),
+    Err(JsonError::UnknownPoint(7))
+  ));
+  assert!(matches!(
+    DijkstraGraph::from_json(r#"{ "version": 1, "points": [{ "id": 0, "x": 1.0, "y": 1.0 }], "lines": [], "end": 4 }"#),
+    Err(JsonError::UnknownPoint(4))
+  ));
+}
@@ -0,0 +1,214 @@
+use super::{ForceLayout, Layering};
+use crate::DijkstraGraph;
+
+const ORIGIN: (f32, f32) = (100., 50.);
+const SIZE: (f32, f32) = (400., 200.);
+
+fn points(amount: usize) -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..amount
+  { graph.append_point(0., 0.); }
+  return graph;
+}
+
+/// Whether the position lies within the area the layouts are given
+fn is_inside((x, y): (f32, f32)) -> bool
+{ return x >= ORIGIN.0 && x <= ORIGIN.0 + SIZE.0 && y >= ORIGIN.1 && y <= ORIGIN.1 + SIZE.1; }
+
+fn distance(graph: &DijkstraGraph, a: usize, b: usize) -> f32
+{
+  let (a, b) = (graph.get(a).unwrap(), graph.get(b).unwrap());
+  return ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+}
+
+#[test]
+fn connected_points_settle_at_the_spring_length()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(0., 0.);
+  graph.append_point(400., 300.);
+  graph.add_line(0, 1, 7);
+
+  graph.force_layout(&ForceLayout::default(), 300, &[]);
+  assert!((distance(&graph, 0, 1) - 100.).abs() < 10., "{}", distance(&graph, 0, 1));
+}
+
+#[test]
+fn unconnected_points_are_further_apart()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(0., 0.);
+  graph.append_point(10., 0.);
+  graph.append_point(20., 0.);
+  graph.add_line(0, 1, 1);
+
+  graph.force_layout(&ForceLayout::default(), 300, &[]);
+  assert!(distance(&graph, 0, 1) < distance(&graph, 0, 2));
+  assert!(distance(&graph, 0, 1) < distance(&graph, 1, 2));
+}
+
+#[test]
+fn weighted_springs()
+{
+  let path = ||
+  {
+    let mut graph = DijkstraGraph::new();
+    graph.append_point(0., 0.);
+    graph.append_point(50., 20.);
+    graph.append_point(100., -20.);
+    graph.add_line(0, 1, 1);
+    // The sign of the length doesn't matter
+    graph.add_line(1, 2, -3);
+    return graph;
+  };
+
+  let mut graph = path();
+  graph.force_layout(&ForceLayout { weighted: true, ..ForceLayout::default() }, 300, &[]);
+  assert!(distance(&graph, 1, 2) > 1.5 * distance(&graph, 0, 1));
+
+  let mut graph = path();
+  graph.force_layout(&ForceLayout::default(), 300, &[]);
+  assert!((distance(&graph, 1, 2) - distance(&graph, 0, 1)).abs() < 10.);
+}
+
+#[test]
+fn pinned_points_stay()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(0., 0.);
+  graph.append_point(500., 0.);
+  graph.append_point(0., 500.);
+  graph.add_line(0, 1, 1);
+  graph.add_line(1, 2, 1);
+
+  graph.force_layout(&ForceLayout::default(), 100, &[1]);
+  assert_eq!((graph.get(1).unwrap().x, graph.get(1).unwrap().y), (500., 0.));
+  assert!(distance(&graph, 0, 1) < 200.);
+}
+
+#[test]
+fn points_on_top_of_each_other_are_separated()
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..5
+  { graph.append_point(0., 0.); }
+  graph.remove_point(2);
+
+  graph.force_layout(&ForceLayout::default(), 100, &[]);
+  for a in [0, 1, 3, 4]
+  {
+    assert!(graph.get(a).unwrap().x.is_finite() && graph.get(a).unwrap().y.is_finite());
+    for b in [0, 1, 3, 4].into_iter().filter(|b| *b != a)
+    { assert!(distance(&graph, a, b) > 10.); }
+  }
+}
+
+#[test]
+fn settled_layouts_hardly_move()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+  graph.force_layout(&ForceLayout::default(), 500, &[]);
+
+  let layout = ForceLayout::default();
+  let moved = graph.force_layout_step(&layout, 10., &[]);
+  assert!(moved < 10., "{}", moved);
+  assert_eq!(DijkstraGraph::new().force_layout_step(&layout, 10., &[]), 0.);
+}
+
+#[test]
+fn positions()
+{
+  let mut graph = points(3);
+  graph.remove_point(1);
+  let revision = graph.revision();
+
+  graph.set_positions(&[Some((5., 6.)), Some((7., 8.)), None, Some((9., 10.))]);
+  assert_eq!(graph.positions(), vec![Some((5., 6.)), None, Some((0., 0.))]);
+  assert_eq!(graph.revision(), revision);
+}
+
+#[test]
+fn circle()
+{
+  let mut graph = points(5);
+  graph.remove_point(3);
+  let positions = graph.circle_layout(ORIGIN, SIZE);
+
+  assert_eq!(positions[3], None);
+  // The circle fits into the height, the first point is at the top
+  assert!(distance_between(positions[0].unwrap(), (300., 50.)) < 0.01);
+  for position in positions.iter().flatten()
+  { assert!((distance_between(*position, (300., 150.)) - 100.).abs() < 0.01); }
+  // Clockwise on the screen, where y grows downwards
+  assert!(positions[1].unwrap().0 > 300. && positions[2].unwrap().1 > 150.);
+
+  assert_eq!(points(1).circle_layout(ORIGIN, SIZE), vec![Some((300., 150.))]);
+}
+
+#[test]
+fn grid()
+{
+  let positions = points(8).grid_layout(ORIGIN, SIZE);
+
+  // Twice as wide as high makes 4 columns and 2 rows
+  assert_eq!(positions[0], Some((150., 100.)));
+  assert_eq!(positions[3], Some((450., 100.)));
+  assert_eq!(positions[4], Some((150., 200.)));
+  assert!(positions.iter().flatten().all(|position| is_inside(*position)));
+  assert!(points(0).grid_layout(ORIGIN, SIZE).is_empty());
+}
+
+#[test]
+fn layers_by_depth()
+{
+  let mut graph = points(5);
+  graph.add_line(0, 1, 1);
+  graph.add_line(1, 2, 1);
+  graph.add_line(0, 3, 1);
+  assert_eq!(graph.layered_layout(Layering::Depth, ORIGIN, SIZE), None);
+
+  graph.set_start(0);
+  let positions = graph.layered_layout(Layering::Depth, ORIGIN, SIZE).unwrap();
+  let x = |id: usize| positions[id].unwrap().0;
+  assert!(x(0) < x(1) && x(1) < x(2));
+  assert_eq!(x(1), x(3));
+  // Point 4 can't be reached, it comes last
+  assert!(x(4) > x(2));
+  assert!(positions.iter().flatten().all(|position| is_inside(*position)));
+}
+
+#[test]
+fn layers_by_rank()
+{
+  let mut graph = points(5);
+  graph.add_line(0, 1, 1);
+  graph.add_line(1, 2, 1);
+  graph.add_line(2, 1, 1);
+  graph.add_line(2, 3, 1);
+
+  let positions = graph.layered_layout(Layering::Rank, ORIGIN, SIZE).unwrap();
+  let x = |id: usize| positions[id].unwrap().0;
+  // 1 and 2 form a cycle and share a layer
+  assert_eq!(x(1), x(2));
+  assert!(x(0) < x(1) && x(2) < x(3));
+  assert_eq!(x(4), x(0));
+}
+
+#[test]
+fn layers_avoid_crossings()
+{
+  let mut graph = points(4);
+  graph.add_line(0, 3, 1);
+  graph.add_line(1, 2, 1);
+
+  let positions = graph.layered_layout(Layering::Rank, ORIGIN, SIZE).unwrap();
+  let y = |id: usize| positions[id].unwrap().1;
+  assert!(y(0) < y(1));
+  // Ordered by id 2 would be above 3 and the lines would cross
+  assert!(y(3) < y(2));
+}
+
+fn distance_between(a: (f32, f32), b: (f32, f32)) -> f32
+{ return ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt(); }
@@ -0,0 +1,264 @@
+use super::{DijkstraSearch, SearchEvent};
+use crate::DijkstraGraph;
+
+/// 0 --2--> 1 --1--> 2
+/// 0 -------5------> 2
+fn triangle() -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(100., 100.);
+  graph.append_point(200., 100.);
+  graph.append_point(300., 100.);
+  graph.add_line(0, 1, 2);
+  graph.add_line(1, 2, 1);
+  graph.add_line(0, 2, 5);
+  graph.set_start(0);
+  graph.set_end(2);
+  return graph;
+}
+
+#[test]
+fn events()
+{
+  use SearchEvent::*;
+
+  let mut graph = triangle();
+  let mut search = DijkstraSearch::new(&mut graph).unwrap();
+
+  let mut events = vec![];
+  while let Some(event) = search.step(&mut graph) { events.push(event); }
+
+  assert_eq!(events, vec![
+    Pop { node: 0, distance: 0 },
+    Relax { from: 0, to: 1, distance: 2 },
+    Update { node: 1, distance: 2, parent: 0 },
+    Relax { from: 0, to: 2, distance: 5 },
+    Update { node: 2, distance: 5, parent: 0 },
+    Pop { node: 1, distance: 2 },
+    Relax { from: 1, to: 2, distance: 3 },
+    Update { node: 2, distance: 3, parent: 1 },
+    Pop { node: 2, distance: 3 },
+  ]);
+  assert!(search.is_finished());
+  assert_eq!(graph.get_path(), Some(vec![0, 1, 2]));
+}
+
+#[test]
+fn intermediate_state()
+{
+  let mut graph = triangle();
+  let mut search = DijkstraSearch::new(&mut graph).unwrap();
+
+  // Popping the start and relaxing both of its edges
+  for _ in 0..5 { search.step(&mut graph); }
+
+  assert_eq!(search.current(), Some(0));
+  assert!(graph.get(0).unwrap().visited());
+  assert_eq!(search.frontier(&graph), vec![1, 2]);
+  assert_eq!(graph.get(1).unwrap().distance(), Some(2));
+  assert_eq!(graph.get(2).unwrap().distance(), Some(5));
+  assert_eq!(graph.get(2).unwrap().parent(), Some(0));
+}
+
+#[test]
+fn editing_stops_the_search()
+{
+  let mut graph = triangle();
+  let mut search = DijkstraSearch::new(&mut graph).unwrap();
+  search.step(&mut graph);
+
+  graph.add_line(2, 0, 1);
+
+  assert!(!search.is_valid(&graph));
+  assert_eq!(search.step(&mut graph), None);
+  assert!(search.is_finished());
+}
+
+#[test]
+fn start_and_end_are_required()
+{
+  let mut graph = triangle();
+  graph.clear_end();
+  assert!(DijkstraSearch::new(&mut graph).is_none());
+
+  graph.set_end(2);
+  graph.remove_point(0);
+  assert!(DijkstraSearch::new(&mut graph).is_none());
+  assert_eq!(graph.start(), None);
+}
+
+/// The length of the found path has to match the distance computed by simply relaxing every line until nothing
+/// changes anymore
+#[test]
+fn random_graphs_match_reference()
+{
+  use rand::{rngs::StdRng, Rng, SeedableRng};
+
+  let mut rng = StdRng::seed_from_u64(7);
+
+  for _ in 0..50
+  {
+    let amount_of_points = rng.gen_range(2..40);
+    let mut graph = DijkstraGraph::new();
+    for id in 0..amount_of_points
+    { graph.add_point(id, 0., 0.); }
+    for _ in 0..amount_of_points * 3
+    { graph.add_line(rng.gen_range(0..amount_of_points), rng.gen_range(0..amount_of_points), rng.gen_range(1..=20)); }
+
+    let mut reference = vec![None; amount_of_points];
+    reference[0] = Some(0_i64);
+    let mut changed = true;
+    while changed
+    {
+      changed = false;
+      for (from, _, distance, to, _) in graph.lines()
+      {
+        let Some(from_distance) = reference[from] else { continue; };
+        if reference[to].is_none_or(|to_distance| to_distance > from_distance + distance as i64)
+        {
+          reference[to] = Some(from_distance + distance as i64);
+          changed = true;
+        }
+      }
+    }
+
+    graph.set_start(0);
+    graph.set_end(amount_of_points - 1);
+    graph.find_shortest_path();
+
+    let path_length = graph.get_path().map(|path|
+    {
+      path.iter().zip(path.iter().skip(1))
+        .map(|(from, to)|
+        {
+          graph.get(*from).unwrap().edges().iter()
+            .find(|edge| edge.destination() == *to)
+            .unwrap()
+            .distance() as i64
+        })
+        .sum::<i64>()
+    });
+
+    assert_eq!(path_length, reference[amount_of_points - 1]);
+  }
+}
+
+#[test]
+fn tie_break_lowest_id()
+{
+  use crate::TieBreak;
+
+  let mut graph = DijkstraGraph::new();
+  graph.insert_medium_graph();
+  graph.set_tie_break(TieBreak::LowestId);
+  graph.set_start(3);
+  graph.set_end(1);
+
+  for _ in 0..10
+  {
+    graph.find_shortest_path();
+    assert_eq!(graph.get_path(), Some(vec![3, 10, 7, 4, 1]));
+  }
+
+  // Point 4 can be reached from 7 and 8 with the same distance
+  graph.set_end(0);
+  graph.find_shortest_path();
+  assert_eq!(graph.get_path(), Some(vec![3, 10, 7, 4, 0]));
+}
+
+#[test]
+fn tie_break_fewest_hops()
+{
+  use crate::TieBreak;
+
+  let mut graph = DijkstraGraph::new();
+  graph.insert_medium_graph();
+  graph.set_tie_break(TieBreak::FewestHops);
+  graph.set_start(3);
+  graph.set_end(1);
+
+  for _ in 0..10
+  {
+    graph.find_shortest_path();
+    assert_eq!(graph.get_path(), Some(vec![3, 10, 7, 1]));
+  }
+}
+
+#[test]
+fn tie_break_seeded_random()
+{
+  use crate::TieBreak;
+
+  let mut graph = DijkstraGraph::new();
+  graph.insert_medium_graph();
+  graph.set_start(3);
+  graph.set_end(1);
+
+  let mut paths = vec![];
+  for seed in 0..20
+  {
+    graph.set_tie_break(TieBreak::SeededRandom(seed));
+    graph.find_shortest_path();
+    let path = graph.get_path().unwrap();
+
+    // The same seed always gives the same path
+    for _ in 0..5
+    {
+      graph.find_shortest_path();
+      assert_eq!(graph.get_path().unwrap(), path);
+    }
+
+    if !paths.contains(&path) { paths.push(path); }
+  }
+
+  // Different seeds find different paths
+  assert!(paths.len() > 1);
+}
+
+#[test]
+fn tie_breaks_on_zero_length_cycles()
+{
+  use crate::TieBreak;
+
+  // 3 --5--> 2 --10--> 0, with 2 and 1 connected by lines of length 0 in both directions
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..4
+  { graph.append_point(0., 0.); }
+  graph.add_line(3, 2, 5);
+  graph.add_line(2, 1, 0);
+  graph.add_line(1, 2, 0);
+  graph.add_line(2, 0, 10);
+  graph.set_start(3);
+  graph.set_end(0);
+
+  // A visited point keeps its parent, otherwise 1 and 2 would become each other's parents
+  for tie_break in [TieBreak::LowestId, TieBreak::FewestHops, TieBreak::SeededRandom(1), TieBreak::SeededRandom(7)]
+  {
+    graph.set_tie_break(tie_break);
+    graph.find_shortest_path();
+    assert_eq!(graph.get_path(), Some(vec![3, 2, 0]), "{:?}", tie_break);
+  }
+}
+
+#[test]
+fn self_loops_are_never_part_of_a_path()
+{
+  use crate::PathAlgorithm;
+
+  // 2 --5--> 1 --1--> 0, 1 has a self-loop of length 0
+  let mut graph = DijkstraGraph::new();
+  for id in 0..3
+  { graph.append_point(id as f32 * 100., 0.); }
+  graph.add_line(2, 1, 5);
+  graph.add_line(1, 1, 0);
+  graph.add_line(1, 0, 1);
+  graph.set_start(2);
+  graph.set_end(0);
+
+  for path_algorithm in [PathAlgorithm::Dijkstra, PathAlgorithm::AStar { scale: 0.01 }, PathAlgorithm::BellmanFord]
+  {
+    graph.set_path_algorithm(path_algorithm);
+    graph.find_shortest_path();
+    assert_eq!(graph.get_path(), Some(vec![2, 1, 0]), "{:?}", path_algorithm);
+  }
+}
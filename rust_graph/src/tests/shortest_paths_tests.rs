@@ -0,0 +1,108 @@
+use crate::DijkstraGraph;
+
+#[test]
+fn distances()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+
+  let distances = graph.distances_from(2);
+  assert_eq!(distances, vec![Some(13), Some(9), Some(0), Some(7), Some(10), Some(5), Some(4), Some(9)]);
+
+  // Nothing can be reached from 0
+  assert_eq!(graph.distances_from(0)[0], Some(0));
+  assert!(graph.distances_from(0).iter().skip(1).all(|distance| distance.is_none()));
+}
+
+#[test]
+fn all_paths_small()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+  graph.set_start(2);
+  graph.set_end(0);
+
+  assert_eq!(graph.all_shortest_paths(100), vec![vec![2, 3, 4, 0], vec![2, 5, 7, 0]]);
+  assert_eq!(graph.shortest_path_lines(), vec![(2, 3), (2, 5), (3, 4), (4, 0), (5, 7), (7, 0)]);
+}
+
+#[test]
+fn all_paths_medium()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_medium_graph();
+  graph.set_start(3);
+  graph.set_end(1);
+
+  assert_eq!(
+    graph.all_shortest_paths(100),
+    vec![
+      vec![3, 9, 8, 4, 1],
+      vec![3, 10, 7, 1],
+      vec![3, 10, 7, 4, 1],
+    ]
+  );
+
+  // The limit is respected
+  assert_eq!(graph.all_shortest_paths(2).len(), 2);
+
+  // Every path found by Dijkstra's algorithm is one of them
+  graph.find_shortest_path();
+  assert!(graph.all_shortest_paths(100).contains(&graph.get_path().unwrap()));
+}
+
+#[test]
+fn no_paths()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+  graph.set_start(0);
+  graph.set_end(2);
+
+  assert!(graph.all_shortest_paths(100).is_empty());
+  assert!(graph.shortest_path_lines().is_empty());
+
+  graph.set_end(0);
+  assert_eq!(graph.all_shortest_paths(100), vec![vec![0]]);
+}
+
+#[test]
+fn zero_length_cycle()
+{
+  let mut graph = DijkstraGraph::new();
+  for id in 0..4 { graph.add_point(id, 0., 0.); }
+  graph.add_line(0, 1, 1);
+  graph.add_line(1, 2, 0);
+  graph.add_line(2, 1, 0);
+  graph.add_line(2, 3, 1);
+  graph.add_line(1, 3, 1);
+  graph.set_start(0);
+  graph.set_end(3);
+
+  assert_eq!(graph.all_shortest_paths(100), vec![vec![0, 1, 2, 3], vec![0, 1, 3]]);
+  // 2 → 1 is only part of shortest walks that go around the cycle
+  assert_eq!(graph.shortest_path_lines(), vec![(0, 1), (1, 2), (1, 3), (2, 1), (2, 3)]);
+}
+
+#[test]
+fn no_paths_without_a_limit()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+  graph.set_start(2);
+  graph.set_end(0);
+
+  assert!(graph.all_shortest_paths(0).is_empty());
+  assert_eq!(graph.all_shortest_paths(1).len(), 1);
+}
+
+#[test]
+fn distances_to()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+
+  // Every distance to 0 has to match the distance from that node
+  for (id, distance) in graph.distances_to(0).iter().enumerate()
+  { assert_eq!(*distance, graph.distances_from(id)[0], "{}", id); }
+}
@@ -0,0 +1,111 @@
+use super::{SpanningForest, SpanningTreeAlgorithm};
+use crate::DijkstraGraph;
+
+const ALGORITHMS: [SpanningTreeAlgorithm; 2] = [SpanningTreeAlgorithm::Prim, SpanningTreeAlgorithm::Kruskal];
+
+#[test]
+fn small_graph()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+
+  for algorithm in ALGORITHMS
+  {
+    let forest = graph.minimum_spanning_forest(algorithm);
+    assert_eq!(forest.total_weight, 28, "{:?}", algorithm);
+    assert_eq!(forest.lines.len(), 7, "{:?}", algorithm);
+    assert!(forest.is_tree(), "{:?}", algorithm);
+  }
+
+  let mut kruskal_lines = graph.minimum_spanning_forest(SpanningTreeAlgorithm::Kruskal).lines;
+  kruskal_lines.sort_unstable();
+  assert_eq!(kruskal_lines, vec![(0, 1, 5), (0, 4, 3), (0, 7, 4), (1, 6, 5), (2, 6, 4), (3, 4, 3), (5, 7, 4)]);
+}
+
+#[test]
+fn directions_are_ignored()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.append_point(0., 0.);
+  graph.append_point(0., 0.);
+  graph.append_point(0., 0.);
+
+  graph.add_line(0, 1, 9);
+  graph.add_line(1, 0, 2);
+  graph.add_line(2, 1, 4);
+  graph.add_line(2, 2, 1);
+
+  assert_eq!(graph.undirected_lines(), vec![(0, 1, 2), (1, 2, 4)]);
+
+  for algorithm in ALGORITHMS
+  {
+    let forest = graph.minimum_spanning_forest(algorithm);
+    assert_eq!(forest.total_weight, 6, "{:?}", algorithm);
+    assert!(forest.contains(1, 0) && forest.contains(2, 1), "{:?}", algorithm);
+  }
+}
+
+#[test]
+fn disconnected_graph()
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..6
+  { graph.append_point(0., 0.); }
+
+  graph.add_line(0, 1, 3);
+  graph.add_line(1, 2, 1);
+  graph.add_line(2, 0, 1);
+  graph.add_line(3, 4, 7);
+  // 5 stays on its own
+
+  for algorithm in ALGORITHMS
+  {
+    let forest = graph.minimum_spanning_forest(algorithm);
+    assert_eq!(forest.trees, 3, "{:?}", algorithm);
+    assert!(!forest.is_tree(), "{:?}", algorithm);
+    assert_eq!(forest.total_weight, 9, "{:?}", algorithm);
+  }
+}
+
+#[test]
+fn removed_points_are_skipped()
+{
+  let mut graph = DijkstraGraph::new();
+  graph.insert_small_graph();
+  graph.remove_point(0);
+
+  for algorithm in ALGORITHMS
+  {
+    let forest = graph.minimum_spanning_forest(algorithm);
+    assert!(forest.lines.iter().all(|(a, b, _)| *a != 0 && *b != 0), "{:?}", algorithm);
+    assert_eq!(forest.lines.len(), 6, "{:?}", algorithm);
+    assert!(forest.is_tree(), "{:?}", algorithm);
+  }
+
+  assert_eq!(DijkstraGraph::new().minimum_spanning_forest(SpanningTreeAlgorithm::Prim), SpanningForest::default());
+}
+
+/// Both algorithms have to find forests of the same weight, even though they may pick different lines
+#[test]
+fn random_graphs_agree()
+{
+  use rand::{rngs::StdRng, Rng, SeedableRng};
+
+  let mut rng = StdRng::seed_from_u64(9);
+
+  for _ in 0..50
+  {
+    let amount_of_points = rng.gen_range(1..40);
+    let mut graph = DijkstraGraph::new();
+    for id in 0..amount_of_points
+    { graph.add_point(id, 0., 0.); }
+    for _ in 0..amount_of_points * 2
+    { graph.add_line(rng.gen_range(0..amount_of_points), rng.gen_range(0..amount_of_points), rng.gen_range(1..=20)); }
+
+    let prim = graph.minimum_spanning_forest(SpanningTreeAlgorithm::Prim);
+    let kruskal = graph.minimum_spanning_forest(SpanningTreeAlgorithm::Kruskal);
+
+    assert_eq!(prim.total_weight, kruskal.total_weight);
+    assert_eq!(prim.trees, kruskal.trees);
+  }
+}
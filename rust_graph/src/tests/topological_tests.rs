@@ -0,0 +1,66 @@
+use super::CriticalPath;
+use crate::DijkstraGraph;
+
+/// Tasks 0 and 1 come first, 2 depends on both of them, 3 on 0 and 4 on 2 and 3
+fn tasks() -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..5
+  { graph.append_point(0., 0.); }
+  graph.add_line(1, 2, 4);
+  graph.add_line(0, 2, 3);
+  graph.add_line(0, 3, 1);
+  graph.add_line(2, 4, 2);
+  graph.add_line(3, 4, 9);
+  return graph;
+}
+
+#[test]
+fn order_and_ranks()
+{
+  let order = tasks().topological_order().unwrap();
+
+  assert_eq!(order.order, vec![0, 1, 2, 3, 4]);
+  assert_eq!(order.positions, vec![Some(0), Some(1), Some(2), Some(3), Some(4)]);
+  assert_eq!(order.ranks, vec![Some(0), Some(0), Some(1), Some(1), Some(2)]);
+}
+
+#[test]
+fn lowest_id_first()
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..4
+  { graph.append_point(0., 0.); }
+  graph.add_line(3, 0, 1);
+  graph.add_line(2, 1, 1);
+  graph.remove_point(2);
+
+  let order = graph.topological_order().unwrap();
+  assert_eq!(order.order, vec![1, 3, 0]);
+  assert_eq!(order.ranks, vec![Some(1), Some(0), None, Some(0)]);
+}
+
+#[test]
+fn cycles()
+{
+  let mut graph = tasks();
+  graph.add_line(4, 1, 1);
+  assert_eq!(graph.topological_order(), Err(vec![1, 2, 4]));
+  assert_eq!(graph.critical_path(), Err(vec![1, 2, 4]));
+
+  let mut graph = tasks();
+  graph.add_line(3, 3, 1);
+  assert_eq!(graph.topological_order(), Err(vec![3]));
+}
+
+#[test]
+fn critical_path()
+{
+  assert_eq!(tasks().critical_path(), Ok(CriticalPath { path: vec![0, 3, 4], length: 10 }));
+
+  let mut graph = tasks();
+  graph.add_line(3, 4, -9);
+  assert_eq!(graph.critical_path(), Ok(CriticalPath { path: vec![1, 2, 4], length: 6 }));
+
+  assert_eq!(DijkstraGraph::new().critical_path(), Ok(CriticalPath::default()));
+}
@@ -0,0 +1,87 @@
+use super::{LineKind::*, TraversalKind};
+use crate::DijkstraGraph;
+
+/// 0 → 1 → 3 → 0, 0 → 2 → 3, 0 → 3 and 4 → 0, which can't be reached from 0
+fn diamond() -> DijkstraGraph
+{
+  let mut graph = DijkstraGraph::new();
+  for _ in 0..5
+  { graph.append_point(0., 0.); }
+  // Added out of order on purpose, the neighbours are visited by id
+  graph.add_line(0, 3, 1);
+  graph.add_line(0, 2, 1);
+  graph.add_line(0, 1, 1);
+  graph.add_line(1, 3, 1);
+  graph.add_line(2, 3, 1);
+  graph.add_line(3, 0, 1);
+  graph.add_line(4, 0, 1);
+  return graph;
+}
+
+#[test]
+fn breadth_first()
+{
+  let traversal = diamond().traverse(0, TraversalKind::BreadthFirst);
+
+  assert_eq!(traversal.discovery_order, vec![0, 1, 2, 3]);
+  assert_eq!(traversal.finish_order, vec![0, 1, 2, 3]);
+  assert_eq!(traversal.levels, vec![Some(0), Some(1), Some(1), Some(1), None]);
+  assert_eq!(traversal.parents, vec![None, Some(0), Some(0), Some(0), None]);
+  assert_eq!(traversal.lines, vec![(0, 1, Tree), (0, 2, Tree), (0, 3, Tree), (1, 3, Cross), (2, 3, Cross), (3, 0, Back)]);
+}
+
+#[test]
+fn depth_first()
+{
+  let traversal = diamond().traverse(0, TraversalKind::DepthFirst);
+
+  assert_eq!(traversal.discovery_order, vec![0, 1, 3, 2]);
+  assert_eq!(traversal.finish_order, vec![3, 1, 2, 0]);
+  assert_eq!(traversal.discovered, vec![Some(0), Some(1), Some(3), Some(2), None]);
+  assert_eq!(traversal.finished, vec![Some(3), Some(1), Some(2), Some(0), None]);
+  assert_eq!(traversal.levels, vec![Some(0), Some(1), Some(1), Some(2), None]);
+  assert_eq!(traversal.lines, vec![(0, 1, Tree), (1, 3, Tree), (3, 0, Back), (0, 2, Tree), (2, 3, Cross), (0, 3, Forward)]);
+}
+
+#[test]
+fn self_loops_and_missing_start()
+{
+  let mut graph = diamond();
+  graph.add_line(2, 2, 1);
+
+  for kind in [TraversalKind::BreadthFirst, TraversalKind::DepthFirst]
+  {
+    assert!(graph.traverse(0, kind).lines.contains(&(2, 2, Back)), "{:?}", kind);
+    assert!(graph.traverse(9, kind).discovery_order.is_empty(), "{:?}", kind);
+  }
+}
+
+/// A long chain must not overflow the stack
+#[test]
+fn long_chain()
+{
+  let mut graph = DijkstraGraph::new();
+  for id in 0..100_000
+  {
+    graph.add_point(id, 0., 0.);
+    if id > 0 { graph.add_line(id - 1, id, 1); }
+  }
+
+  let traversal = graph.traverse(0, TraversalKind::DepthFirst);
+  assert_eq!(traversal.levels[99_999], Some(99_999));
+  assert_eq!(traversal.finish_order[0], 99_999);
+}
+
+#[test]
+fn undirected_lines_are_looked_at_once()
+{
+  let mut graph = diamond();
+  graph.set_undirected(true);
+
+  let traversal = graph.traverse(0, TraversalKind::DepthFirst);
+  assert_eq!(traversal.discovery_order, vec![0, 1, 3, 2, 4]);
+  assert_eq!(traversal.lines, vec![(0, 1, Tree), (1, 3, Tree), (3, 0, Back), (3, 2, Tree), (2, 0, Back), (0, 4, Tree)]);
+
+  let traversal = graph.traverse(0, TraversalKind::BreadthFirst);
+  assert_eq!(traversal.lines, vec![(0, 1, Tree), (0, 2, Tree), (0, 3, Tree), (0, 4, Tree), (1, 3, Cross), (2, 3, Cross)]);
+}
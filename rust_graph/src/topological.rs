@@ -0,0 +1,154 @@
+//! Topological ordering with Kahn's algorithm and the critical path of a graph without cycles.
+//!
+//! A topological order lists every node before all nodes its lines lead to, like tasks before the tasks that depend
+//! on them. It only exists if the graph has no cycle, otherwise one of the cycles is returned instead.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::DijkstraGraph;
+
+/// A topological order of the graph, all lists that are indexed by id are `None` for removed nodes
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TopologicalOrder
+{
+  /// The ids of all nodes, every line leads from a node to one that comes later. Among the nodes that could come next
+  /// the one with the lowest id is picked.
+  pub order: Vec<usize>,
+  /// The position of every node in `order`, indexed by id
+  pub positions: Vec<Option<usize>>,
+  /// The most lines on any path that ends at the node, indexed by id. Nodes without incoming lines have rank 0 and
+  /// every line leads to a higher rank.
+  pub ranks: Vec<Option<usize>>,
+}
+
+/// The path with the largest total length
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CriticalPath
+{
+  /// The ids of the nodes on the path, empty for an empty graph
+  pub path: Vec<usize>,
+  /// The sum of the lengths of the lines on the path
+  pub length: i64,
+}
+
+impl DijkstraGraph
+{
+  /// The ids of the existing nodes with a line to every node, indexed by id
+  fn predecessors(&self) -> Vec<Vec<usize>>
+  {
+    let mut predecessors = vec![vec![]; self.points().len()];
+    self.lines().iter().for_each(|(from, _, _, to, _)| predecessors[*to].push(*from));
+    return predecessors;
+  }
+
+  /// Orders the nodes topologically with Kahn's algorithm. If the graph has a cycle the ids on it are returned
+  /// instead, starting at the lowest one, every id has a line to the next one and the last one back to the first.
+  pub fn topological_order(&self) -> Result<TopologicalOrder, Vec<usize>>
+  {
+    let amount = self.points().len();
+    let predecessors = self.predecessors();
+    let mut incoming: Vec<usize> = predecessors.iter().map(|predecessors| predecessors.len()).collect();
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..amount)
+      .filter(|id| self.get(*id).is_some() && incoming[*id] == 0)
+      .map(Reverse)
+      .collect();
+
+    let mut order = TopologicalOrder
+    {
+      order: vec![],
+      positions: vec![None; amount],
+      ranks: vec![None; amount],
+    };
+
+    while let Some(Reverse(id)) = ready.pop()
+    {
+      order.positions[id] = Some(order.order.len());
+      order.order.push(id);
+      // All predecessors have been placed already
+      order.ranks[id] = Some(predecessors[id].iter().map(|from| order.ranks[*from].unwrap() + 1).max().unwrap_or(0));
+
+      for edge in self.get(id).unwrap().edges().iter().filter(|edge| self.get(edge.destination()).is_some())
+      {
+        incoming[edge.destination()] -= 1;
+        if incoming[edge.destination()] == 0 { ready.push(Reverse(edge.destination())); }
+      }
+    }
+
+    if order.order.len() == self.size() { return Ok(order); }
+    return Err(self.find_cycle(&order.positions, &predecessors));
+  }
+
+  /// Finds a cycle among the nodes Kahn's algorithm couldn't place. Each of them still has a predecessor that hasn't
+  /// been placed either, so going backwards over them has to run into a node twice.
+  fn find_cycle(&self, positions: &[Option<usize>], predecessors: &[Vec<usize>]) -> Vec<usize>
+  {
+    let is_left = |id: usize| self.get(id).is_some() && positions[id].is_none();
+
+    let mut current = (0..positions.len()).find(|id| is_left(*id)).unwrap();
+    let mut seen = vec![false; positions.len()];
+    while !seen[current]
+    {
+      seen[current] = true;
+      current = *predecessors[current].iter().filter(|from| is_left(**from)).min().unwrap();
+    }
+
+    let on_cycle = current;
+    let mut cycle = vec![on_cycle];
+    current = *predecessors[on_cycle].iter().filter(|from| is_left(**from)).min().unwrap();
+    while current != on_cycle
+    {
+      cycle.push(current);
+      current = *predecessors[current].iter().filter(|from| is_left(**from)).min().unwrap();
+    }
+
+    // The predecessors point backwards, the cycle starts at its lowest id
+    cycle.reverse();
+    let lowest = cycle.iter().enumerate().min_by_key(|(_, id)| **id).unwrap().0;
+    cycle.rotate_left(lowest);
+    return cycle;
+  }
+
+  /// Finds the path with the largest total length, also called the critical path. A path can start and end at any
+  /// node, if every line is negative it is a single node. Returns a cycle like `topological_order` if there is one,
+  /// since going around it again and again would make the path arbitrarily long.
+  pub fn critical_path(&self) -> Result<CriticalPath, Vec<usize>>
+  {
+    let order = self.topological_order()?;
+    let predecessors = self.predecessors();
+
+    // The length of the longest path ending at every node and the node before it on that path
+    let mut lengths: Vec<i64> = vec![0; self.points().len()];
+    let mut parents: Vec<Option<usize>> = vec![None; self.points().len()];
+    for id in order.order.iter()
+    {
+      for from in predecessors[*id].iter()
+      {
+        let length = lengths[*from] + self.get(*from).unwrap().edges().iter()
+          .find(|edge| edge.destination() == *id)
+          .unwrap()
+          .distance() as i64;
+        if length > lengths[*id]
+        {
+          lengths[*id] = length;
+          parents[*id] = Some(*from);
+        }
+      }
+    }
+
+    // The first node in the order with the longest path wins ties
+    let Some(end) = order.order.iter().copied().rev().max_by_key(|id| lengths[*id]) else
+    { return Ok(CriticalPath::default()); };
+
+    let mut path = vec![end];
+    while let Some(parent) = parents[*path.last().unwrap()]
+    { path.push(parent); }
+    path.reverse();
+
+    return Ok(CriticalPath { path, length: lengths[end] });
+  }
+}
+
+// Tests
+#[path = "./tests/topological_tests.rs"]
+#[cfg(test)]
+mod topological_tests;
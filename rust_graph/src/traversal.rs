@@ -0,0 +1,196 @@
+//! Breadth-first and depth-first traversal from a single node, with the kind of every line it comes across.
+//!
+//! The neighbours of a node are always visited in the order of their ids, so the result doesn't depend on the order
+//! in which the lines have been added. In undirected graphs every line is only looked at once, from the side that is
+//! reached first, so there are only tree, back and cross lines.
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::DijkstraGraph;
+
+/// The order in which the nodes are visited
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TraversalKind
+{
+  /// Visits all nodes one line away from the start, then all nodes two lines away and so on
+  #[default]
+  BreadthFirst,
+  /// Follows every line as deep as possible before going back
+  DepthFirst,
+}
+
+/// The role of a line in a traversal
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineKind
+{
+  /// The line over which a node has been discovered
+  Tree,
+  /// Leads back to an ancestor of the node in the traversal tree, or to the node itself
+  Back,
+  /// Leads to a descendant that has been discovered over another line; only depth-first traversals have these
+  Forward,
+  /// Leads to a node that is neither an ancestor nor a descendant
+  Cross,
+}
+
+/// The result of a traversal, all lists that are indexed by id are `None` for nodes that haven't been reached
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Traversal
+{
+  /// The ids of the nodes in the order they have been discovered
+  pub discovery_order: Vec<usize>,
+  /// The ids of the nodes in the order they have been finished, meaning all their lines have been looked at
+  pub finish_order: Vec<usize>,
+  /// The position of every node in `discovery_order`, indexed by id
+  pub discovered: Vec<Option<usize>>,
+  /// The position of every node in `finish_order`, indexed by id
+  pub finished: Vec<Option<usize>>,
+  /// The amount of tree lines between the start and every node, indexed by id
+  pub levels: Vec<Option<usize>>,
+  /// The node every node has been discovered from, indexed by id; the start has none
+  pub parents: Vec<Option<usize>>,
+  /// Every line leaving a reached node as `(from, to, kind)`, in the order they have been looked at
+  pub lines: Vec<(usize, usize, LineKind)>,
+}
+
+impl Traversal
+{
+  fn new(amount: usize) -> Traversal
+  {
+    return Traversal
+    {
+      discovered: vec![None; amount],
+      finished: vec![None; amount],
+      levels: vec![None; amount],
+      parents: vec![None; amount],
+      ..Traversal::default()
+    };
+  }
+
+  fn discover(&mut self, id: usize, parent: Option<usize>)
+  {
+    self.discovered[id] = Some(self.discovery_order.len());
+    self.discovery_order.push(id);
+    self.parents[id] = parent;
+    self.levels[id] = Some(parent.map_or(0, |parent| self.levels[parent].unwrap() + 1));
+  }
+
+  fn finish(&mut self, id: usize)
+  {
+    self.finished[id] = Some(self.finish_order.len());
+    self.finish_order.push(id);
+  }
+
+  /// Returns true if `ancestor` lies on the tree path from the start to `id`, or is `id` itself
+  fn is_ancestor(&self, ancestor: usize, mut id: usize) -> bool
+  {
+    loop
+    {
+      if id == ancestor { return true; }
+      let Some(parent) = self.parents[id] else { return false; };
+      id = parent;
+    }
+  }
+}
+
+/// Returns false if the line has already been looked at from its other end in an undirected graph. The lines that
+/// have been looked at are kept as `(lower id, higher id)`.
+fn look_at(looked_at: &mut HashSet<(usize, usize)>, undirected: bool, from: usize, to: usize) -> bool
+{ return !undirected || looked_at.insert((from.min(to), from.max(to))); }
+
+impl DijkstraGraph
+{
+  /// The ids of the existing nodes the lines of `id` lead to, sorted
+  fn sorted_neighbours(&self, id: usize) -> Vec<usize>
+  {
+    let mut neighbours: Vec<usize> = self.get(id)
+      .map(|point| point.edges().iter().map(|edge| edge.destination()).filter(|to| self.get(*to).is_some()).collect())
+      .unwrap_or_default();
+    neighbours.sort_unstable();
+    return neighbours;
+  }
+
+  /// Traverses the graph from `start` and classifies every line it comes across. Returns an empty traversal if
+  /// `start` doesn't exist.
+  pub fn traverse(&self, start: usize, kind: TraversalKind) -> Traversal
+  {
+    let mut traversal = Traversal::new(self.points().len());
+    if self.get(start).is_none() { return traversal; }
+
+    let mut looked_at = HashSet::new();
+    match kind
+    {
+      TraversalKind::BreadthFirst => self.breadth_first(start, &mut traversal, &mut looked_at),
+      TraversalKind::DepthFirst => self.depth_first(start, &mut traversal, &mut looked_at),
+    }
+
+    return traversal;
+  }
+
+  fn breadth_first(&self, start: usize, traversal: &mut Traversal, looked_at: &mut HashSet<(usize, usize)>)
+  {
+    traversal.discover(start, None);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(from) = queue.pop_front()
+    {
+      for to in self.sorted_neighbours(from)
+      {
+        if !look_at(looked_at, self.is_undirected(), from, to) { continue; }
+
+        let kind = if traversal.discovered[to].is_none()
+        {
+          traversal.discover(to, Some(from));
+          queue.push_back(to);
+          LineKind::Tree
+        }
+        else if traversal.is_ancestor(to, from) { LineKind::Back }
+        else { LineKind::Cross };
+
+        traversal.lines.push((from, to, kind));
+      }
+
+      traversal.finish(from);
+    }
+  }
+
+  fn depth_first(&self, start: usize, traversal: &mut Traversal, looked_at: &mut HashSet<(usize, usize)>)
+  {
+    traversal.discover(start, None);
+    // The nodes on the current tree path with the neighbours that are still left to look at, in reverse
+    let mut stack = vec![(start, self.sorted_neighbours(start).into_iter().rev().collect::<Vec<usize>>())];
+
+    while let Some((from, neighbours)) = stack.last_mut()
+    {
+      let from = *from;
+      let Some(to) = neighbours.pop() else
+      {
+        traversal.finish(from);
+        stack.pop();
+        continue;
+      };
+      if !look_at(looked_at, self.is_undirected(), from, to) { continue; }
+
+      let kind = match (traversal.discovered[to], traversal.finished[to])
+      {
+        (None, _) => LineKind::Tree,
+        // Still on the stack
+        (Some(_), None) => LineKind::Back,
+        (Some(discovered), Some(_)) if discovered > traversal.discovered[from].unwrap() => LineKind::Forward,
+        (Some(_), Some(_)) => LineKind::Cross,
+      };
+      traversal.lines.push((from, to, kind));
+
+      if kind == LineKind::Tree
+      {
+        traversal.discover(to, Some(from));
+        stack.push((to, self.sorted_neighbours(to).into_iter().rev().collect()));
+      }
+    }
+  }
+}
+
+// Tests
+#[path = "./tests/traversal_tests.rs"]
+#[cfg(test)]
+mod traversal_tests;
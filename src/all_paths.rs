@@ -0,0 +1,64 @@
+use rust_graph::DijkstraGraph;
+
+/// More paths than this are not listed
+pub(crate) const MAX_PATHS: usize = 1_000;
+
+/// Every shortest path between the start and the end, one of them is selected to be drawn
+#[derive(Default)]
+pub(crate) struct AllPaths
+{
+  pub(crate) paths: Vec<Vec<usize>>,
+  /// The lines of all shortest paths
+  pub(crate) lines: Vec<(usize, usize)>,
+  /// The index of the path that is drawn
+  pub(crate) index: usize,
+  /// Draws all shortest paths at once instead of the selected one
+  pub(crate) show_all: bool,
+}
+
+impl AllPaths
+{
+  /// Collects all shortest paths, the one found by the path search gets selected
+  pub(crate) fn find(&mut self, graph: &DijkstraGraph)
+  {
+    self.paths = graph.all_shortest_paths(MAX_PATHS);
+    // The lines of the listed paths leave out cycles of lines with length zero, but there may be more paths than that
+    self.lines = if self.paths.len() < MAX_PATHS
+    {
+      let mut lines: Vec<(usize, usize)> = self.paths.iter()
+        .flat_map(|path| path.windows(2).map(|line| (line[0], line[1])))
+        .collect();
+      lines.sort_unstable();
+      lines.dedup();
+      lines
+    }
+    else
+    { graph.shortest_path_lines() };
+    self.index = graph.get_path()
+      .and_then(|path| self.paths.iter().position(|other_path| *other_path == path))
+      .unwrap_or(0);
+  }
+
+  pub(crate) fn clear(&mut self)
+  {
+    self.paths.clear();
+    self.lines.clear();
+    self.index = 0;
+  }
+
+  pub(crate) fn next(&mut self)
+  {
+    if self.paths.is_empty() { return; }
+    self.index = (self.index + 1) % self.paths.len();
+  }
+
+  pub(crate) fn previous(&mut self)
+  {
+    if self.paths.is_empty() { return; }
+    self.index = (self.index + self.paths.len() - 1) % self.paths.len();
+  }
+
+  /// The path that is drawn
+  pub(crate) fn selected(&self) -> Option<&Vec<usize>>
+  { return self.paths.get(self.index); }
+}
@@ -0,0 +1,108 @@
+use rust_graph::{DijkstraGraph, DijkstraSearch, SearchEvent};
+
+/// Plays back Dijkstra's algorithm one event at a time
+pub(crate) struct Animation
+{
+  pub(crate) search: Option<DijkstraSearch>,
+  /// The event that happened during the last step, it gets described in the UI
+  pub(crate) last_event: Option<SearchEvent>,
+  pub(crate) playing: bool,
+  pub(crate) steps_per_second: f32,
+  /// Time that has passed since the last step while playing
+  elapsed: f32,
+}
+
+impl Default for Animation
+{
+  fn default() -> Self
+  {
+    return Animation
+    {
+      search: None,
+      last_event: None,
+      playing: false,
+      steps_per_second: 2.,
+      elapsed: 0.,
+    };
+  }
+}
+
+impl Animation
+{
+  /// Advances the animation by one event, a new search is started if there is none
+  pub(crate) fn step(&mut self, graph: &mut DijkstraGraph)
+  {
+    if self.search.as_ref().is_none_or(|search| search.is_finished())
+    {
+      self.search = DijkstraSearch::new(graph);
+      self.last_event = None;
+    }
+
+    let Some(search) = self.search.as_mut() else { self.stop(); return; };
+
+    self.last_event = search.step(graph);
+    if search.is_finished() { self.playing = false; }
+  }
+
+  pub(crate) fn toggle_playing(&mut self)
+  {
+    self.playing = !self.playing;
+    self.elapsed = 0.;
+  }
+
+  /// Discards the search but keeps the speed
+  pub(crate) fn stop(&mut self)
+  {
+    self.search = None;
+    self.last_event = None;
+    self.playing = false;
+  }
+
+  /// Called once per frame, takes as many steps as the speed demands
+  pub(crate) fn update(&mut self, graph: &mut DijkstraGraph, frame_time: f32)
+  {
+    // Editing the graph invalidates the search
+    if self.search.as_ref().is_some_and(|search| !search.is_valid(graph)) { self.stop(); }
+
+    if !self.playing { return; }
+
+    self.elapsed += frame_time;
+    while self.playing && self.elapsed >= 1. / self.steps_per_second
+    {
+      self.elapsed -= 1. / self.steps_per_second;
+      self.step(graph);
+    }
+  }
+
+  /// The node whose edges are currently being relaxed
+  pub(crate) fn current(&self) -> Option<usize>
+  { return self.search.as_ref()?.current(); }
+
+  /// The edge that has been looked at during the last step
+  pub(crate) fn current_line(&self) -> Option<(usize, usize)>
+  {
+    return match self.last_event?
+    {
+      SearchEvent::Relax { from, to, .. } => Some((from, to)),
+      SearchEvent::Update { node, parent, .. } => Some((parent, node)),
+      SearchEvent::Pop { .. } => None,
+    };
+  }
+
+  /// A human readable description of the last step
+  pub(crate) fn describe(&self) -> String
+  {
+    return match (&self.search, self.last_event)
+    {
+      (None, _) => String::from("Press \"Step\" or \"Play\" to start."),
+      (Some(search), None) if search.is_finished() => String::from("The search is finished."),
+      (Some(_), None) => String::from("The search has started."),
+      (_, Some(SearchEvent::Pop { node, distance })) =>
+        format!("Visiting point {}, its distance {} is final.", node, distance),
+      (_, Some(SearchEvent::Relax { from, to, distance })) =>
+        format!("Checking the line {} → {}, it would give {} a distance of {}.", from, to, to, distance),
+      (_, Some(SearchEvent::Update { node, distance, parent })) =>
+        format!("Point {} is now reached from {} with a distance of {}.", node, parent, distance),
+    };
+  }
+}
@@ -0,0 +1,68 @@
+use macroquad::{camera::Camera2D, math::{Rect, Vec2}, window::{screen_height, screen_width}};
+use rust_graph::DijkstraGraph;
+
+/// The furthest the camera can zoom out
+pub(crate) const MIN_ZOOM: f32 = 0.05;
+/// The furthest the camera can zoom in
+pub(crate) const MAX_ZOOM: f32 = 10.;
+/// How much one notch of the mouse wheel zooms
+pub(crate) const ZOOM_STEP: f32 = 1.1;
+
+/// The part of the graph that is shown on the canvas. The points are stored in world coordinates, the camera maps them
+/// to the screen for painting and the mouse back into the world for hit-testing.
+pub(crate) struct Camera
+{
+  /// The world position shown in the top left corner of the screen
+  pub(crate) offset: Vec2,
+  /// How many pixels one unit of the world takes up
+  pub(crate) zoom: f32,
+}
+
+impl Default for Camera
+{
+  fn default() -> Self
+  { return Camera { offset: Vec2::ZERO, zoom: 1. }; }
+}
+
+impl Camera
+{
+  /// The world position under the given position on the screen
+  pub(crate) fn to_world(&self, (x, y): (f32, f32)) -> (f32, f32)
+  { return (self.offset.x + x / self.zoom, self.offset.y + y / self.zoom); }
+
+  /// The camera macroquad paints the graph with, it has to be set before painting and unset before painting the UI
+  pub(crate) fn macroquad_camera(&self) -> Camera2D
+  { return Camera2D::from_display_rect(Rect::new(self.offset.x, self.offset.y, screen_width() / self.zoom, screen_height() / self.zoom)); }
+
+  /// Zooms in or out by the given factor, the world position under the given screen position stays where it is
+  pub(crate) fn zoom_at(&mut self, screen_position: (f32, f32), factor: f32)
+  {
+    let (x, y) = self.to_world(screen_position);
+    self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+    self.offset = Vec2 { x: x - screen_position.0 / self.zoom, y: y - screen_position.1 / self.zoom };
+  }
+
+  /// Moves the world along with the mouse, the movement is given in pixels
+  pub(crate) fn pan(&mut self, (dx, dy): (f32, f32))
+  { self.offset -= Vec2 { x: dx, y: dy } / self.zoom; }
+
+  /// Zooms and moves the camera so that the whole graph fits into a canvas of the given size in the top left corner of
+  /// the screen, with a margin of `margin` pixels around it
+  pub(crate) fn fit(&mut self, graph: &DijkstraGraph, width: f32, height: f32, margin: f32)
+  {
+    let Some(((left, top), (right, bottom))) = graph.bounding_box() else
+    {
+      *self = Camera::default();
+      return;
+    };
+
+    // A single point or a straight row of points only limits the zoom in one direction or not at all, and small
+    // graphs aren't blown up beyond their actual size
+    let zoom_x = (width - 2. * margin) / (right - left).max(f32::EPSILON);
+    let zoom_y = (height - 2. * margin) / (bottom - top).max(f32::EPSILON);
+    self.zoom = zoom_x.min(zoom_y).clamp(MIN_ZOOM, 1.);
+
+    let center = Vec2 { x: (left + right) / 2., y: (top + bottom) / 2. };
+    self.offset = center - Vec2 { x: width, y: height } / (2. * self.zoom);
+  }
+}
@@ -0,0 +1,47 @@
+use rust_graph::{DijkstraGraph, PathAlgorithm, Positions};
+
+/// Compares how many points Dijkstra's algorithm and A* expand, shown in path mode while A* is selected
+#[derive(Default)]
+pub(crate) struct Comparison
+{
+  /// The amount of expanded points as `(Dijkstra, A*)`, `None` if there is nothing to compare
+  pub(crate) expanded: Option<(usize, usize)>,
+  /// The points whose distance to the end is overestimated by the heuristic
+  pub(crate) inadmissible_points: Vec<usize>,
+  /// The revision of the graph and the positions of its points the comparison has been made for. Moving points doesn't
+  /// change the revision, but the heuristic of A* depends on where they are.
+  computed_for: Option<(u64, Positions)>,
+}
+
+impl Comparison
+{
+  /// Runs both searches again, but only if the graph has changed or points have been moved since the last time
+  pub(crate) fn update(&mut self, graph: &DijkstraGraph)
+  {
+    let key = (graph.revision(), graph.positions());
+    if self.computed_for.as_ref() == Some(&key) { return; }
+    self.computed_for = Some(key);
+
+    let PathAlgorithm::AStar { scale } = graph.path_algorithm() else
+    {
+      self.expanded = None;
+      self.inadmissible_points.clear();
+      return;
+    };
+
+    self.expanded = graph.expanded_points(PathAlgorithm::Dijkstra)
+      .zip(graph.expanded_points(graph.path_algorithm()));
+    self.inadmissible_points = graph.inadmissible_points(scale);
+  }
+
+  /// A human readable warning if A* might not find the shortest path
+  pub(crate) fn warning(&self) -> Option<String>
+  {
+    return match self.inadmissible_points.as_slice()
+    {
+      [] => None,
+      [id] => Some(format!("⚠ The distance from point {} to the end is overestimated, A* might not find the shortest path.", id)),
+      ids => Some(format!("⚠ The distance from {} points to the end is overestimated, A* might not find the shortest path.", ids.len())),
+    };
+  }
+}
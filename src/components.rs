@@ -0,0 +1,55 @@
+use macroquad::color::{hsl_to_rgb, Color};
+use rust_graph::{Components, DijkstraGraph};
+
+/// The strongly connected components drawn in components mode
+#[derive(Default)]
+pub(crate) struct ComponentsView
+{
+  /// Paints the condensation instead of the graph
+  pub(crate) collapsed: bool,
+  pub(crate) components: Components,
+  /// Every component collapsed into a single point, the ids are the indices of the components
+  pub(crate) condensation: DijkstraGraph,
+  /// The revision of the graph the components have been computed for
+  computed_for: Option<u64>,
+}
+
+impl ComponentsView
+{
+  /// Looks for the components again, but only if the graph has changed since the last time
+  pub(crate) fn update(&mut self, graph: &DijkstraGraph)
+  {
+    if self.computed_for == Some(graph.revision()) { return; }
+
+    self.components = graph.strongly_connected_components();
+    self.condensation = graph.condensation(&self.components);
+    self.computed_for = Some(graph.revision());
+  }
+
+  /// The graph that is painted, either the graph itself or its condensation
+  pub(crate) fn painted<'a>(&'a self, graph: &'a DijkstraGraph) -> &'a DijkstraGraph
+  { return if self.collapsed { &self.condensation } else { graph }; }
+
+  /// The colour of the component of a point of the painted graph
+  pub(crate) fn color(&self, id: usize) -> Option<Color>
+  {
+    let component = if self.collapsed { Some(id) } else { *self.components.component_of.get(id)? }?;
+    // Steps around the colour wheel by the golden ratio, so neighbouring components get clearly different hues
+    return Some(hsl_to_rgb((component as f32 * 0.618_034).fract(), 0.8, 0.6));
+  }
+
+  /// A human readable summary of the components
+  pub(crate) fn describe(&self) -> String
+  {
+    return match self.components.len()
+    {
+      0 => String::from("The graph is empty."),
+      1 => String::from("The graph is strongly connected, every point can reach every other point."),
+      components => format!(
+        "The graph has {} components.\nA path can only lead from a component to the ones it has lines to, directly or \
+        through others.",
+        components,
+      ),
+    };
+  }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use rust_graph::{AugmentingPath, DijkstraGraph, MaxFlow};
+
+/// The maximum flow from the start to the end shown in flow mode, either the final one or the one after a given step
+#[derive(Default)]
+pub(crate) struct FlowView
+{
+  /// `None` while the start or end isn't set
+  pub(crate) flow: Option<MaxFlow>,
+  /// The amount of augmenting paths that have been applied, `None` shows the final flow with its minimum cut
+  pub(crate) step: Option<usize>,
+  /// The flow of every line at the step that is shown, in the order of the lines of the graph
+  pub(crate) flows: Vec<i64>,
+  /// The `flow/capacity` label of every line, painted where the length is painted otherwise
+  pub(crate) labels: Vec<String>,
+  /// The revision of the graph and the step the flow has been computed for
+  computed_for: Option<(u64, Option<usize>)>,
+}
+
+impl FlowView
+{
+  /// Computes the flow again, but only if the graph or the step has changed since the last time
+  pub(crate) fn update(&mut self, graph: &DijkstraGraph)
+  {
+    if self.computed_for.map(|(revision, _)| revision) != Some(graph.revision())
+    {
+      self.flow = graph.start().zip(graph.end()).and_then(|(start, end)| graph.max_flow(start, end));
+      self.step = self.step.map(|step| step.min(self.steps()));
+    }
+    if self.computed_for == Some((graph.revision(), self.step)) { return; }
+
+    self.flows = match (&self.flow, self.step)
+    {
+      (None, _) => vec![],
+      (Some(flow), None) => flow.lines.iter().map(|line| line.flow).collect(),
+      (Some(flow), Some(step)) => flow.flows_after(step),
+    };
+    self.labels = self.line_labels(graph.is_undirected());
+    self.computed_for = Some((graph.revision(), self.step));
+  }
+
+  /// The amount of augmenting paths
+  pub(crate) fn steps(&self) -> usize
+  { return self.flow.as_ref().map_or(0, |flow| flow.augmenting_paths.len()); }
+
+  /// Both directions of an undirected line share one label with the flow that is left after they cancel each other
+  fn line_labels(&self, undirected: bool) -> Vec<String>
+  {
+    let Some(flow) = &self.flow else { return vec![]; };
+
+    let indices: HashMap<(usize, usize), usize> = flow.lines.iter().enumerate()
+      .map(|(index, line)| ((line.from, line.to), index))
+      .collect();
+
+    return flow.lines.iter().enumerate()
+      .map(|(index, line)|
+      {
+        let reverse = indices.get(&(line.to, line.from)).filter(|_| undirected && line.from != line.to);
+        let amount = reverse.map_or(self.flows[index], |reverse| (self.flows[index] - self.flows[*reverse]).abs());
+        return format!("{}/{}", amount, line.capacity);
+      })
+      .collect();
+  }
+
+  /// The lines that carry flow at the step that is shown as `(from, to)`
+  pub(crate) fn used_lines(&self) -> Vec<(usize, usize)>
+  {
+    let Some(flow) = &self.flow else { return vec![]; };
+    return flow.lines.iter().zip(self.flows.iter())
+      .filter(|(_, flow)| **flow > 0)
+      .map(|(line, _)| (line.from, line.to))
+      .collect();
+  }
+
+  /// The path the last step has sent flow along, `None` if the final flow is shown or no step has been made yet
+  pub(crate) fn current_path(&self) -> Option<&AugmentingPath>
+  {
+    let step = self.step?.checked_sub(1)?;
+    return self.flow.as_ref()?.augmenting_paths.get(step);
+  }
+}
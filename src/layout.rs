@@ -0,0 +1,99 @@
+use rust_graph::{DijkstraGraph, EditHistory, ForceLayout, Positions};
+
+/// How many steps "Relayout" runs the force-directed layout for
+pub(crate) const RELAYOUT_STEPS: usize = 300;
+/// How much the simulation cools down every frame
+const COOLING: f32 = 0.98;
+/// How long the points take to move to the places of a new layout, in seconds
+const TRANSITION_TIME: f32 = 0.6;
+
+/// The points moving from their old places to the ones of a new layout
+struct Transition
+{
+  from: Positions,
+  to: Positions,
+  elapsed: f32,
+  /// Where the transition has left the points, it is called off if they are moved or the graph changes on the way
+  left_at: Positions,
+}
+
+/// The force-directed layout, either run all at once or simulated live one step per frame, and the transition to a
+/// new layout
+#[derive(Default)]
+pub(crate) struct LayoutView
+{
+  pub(crate) layout: ForceLayout,
+  pub(crate) simulating: bool,
+  /// How far the points can move during the next frame of the simulation
+  temperature: f32,
+  /// The revision of the graph the simulation has been heated up for
+  heated_for: Option<u64>,
+  transition: Option<Transition>,
+}
+
+impl LayoutView
+{
+  /// Moves the points along the transition to a new layout, or by one step of the simulation while it runs. Changing
+  /// the graph or dragging points heats the simulation up again, so that the points can find their new places. The
+  /// dragged points are pinned to the mouse.
+  pub(crate) fn update(&mut self, graph: &mut DijkstraGraph, dragged_points: &[usize], frame_time: f32)
+  {
+    if let Some(transition) = self.transition.as_mut()
+    {
+      transition.elapsed += frame_time;
+      let t = (transition.elapsed / TRANSITION_TIME).min(1.);
+      // Starts and ends slowly
+      let t = t * t * (3. - 2. * t);
+
+      if graph.positions() != transition.left_at
+      {
+        self.transition = None;
+        return;
+      }
+
+      let positions: Positions = transition.from.iter().zip(transition.to.iter())
+        .map(|(from, to)|
+        {
+          let (from, to) = ((*from)?, (*to)?);
+          return Some((from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t));
+        })
+        .collect();
+      graph.set_positions(&positions);
+      transition.left_at = graph.positions();
+      if t >= 1.
+      { self.transition = None; }
+      return;
+    }
+
+    if !self.simulating
+    {
+      self.heated_for = None;
+      return;
+    }
+
+    if self.heated_for != Some(graph.revision()) || !dragged_points.is_empty()
+    {
+      self.temperature = self.layout.spring_length / 4.;
+      self.heated_for = Some(graph.revision());
+    }
+    graph.force_layout_step(&self.layout, self.temperature, dragged_points);
+    self.temperature *= COOLING;
+  }
+
+  /// Moves the points to the given places, one edit that can be undone. The points don't jump there but move over
+  /// the next frames.
+  pub(crate) fn arrange(&mut self, graph: &mut DijkstraGraph, history: &mut EditHistory, positions: Positions)
+  {
+    let from = graph.positions();
+    history.set_positions(graph, &positions);
+    self.transition = Some(Transition { from, to: positions, elapsed: 0., left_at: graph.positions() });
+  }
+
+  /// Runs the whole force-directed layout at once, starting from the current positions
+  pub(crate) fn relayout(&mut self, graph: &mut DijkstraGraph, history: &mut EditHistory)
+  {
+    let mut arranged = graph.clone();
+    arranged.force_layout(&self.layout, RELAYOUT_STEPS, &[]);
+    self.arrange(graph, history, arranged.positions());
+  }
+}
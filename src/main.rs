@@ -1,4 +1,15 @@
-mod graph;
+mod all_paths;
+mod animation;
+mod camera;
+mod comparison;
+mod components;
+mod flow;
+mod layout;
+mod matrix_view;
+mod selection;
+mod spanning_tree;
+mod topological;
+mod traversal;
 mod ui;
 mod utils;
 #[path ="icons/small.rs"]
@@ -8,8 +19,20 @@ mod medium_icon;
 #[path ="icons/big.rs"]
 mod big_icon;
 
-use egui_macroquad::draw;
-use graph::*;
+use all_paths::AllPaths;
+use animation::Animation;
+use camera::{Camera, ZOOM_STEP};
+use comparison::Comparison;
+use components::ComponentsView;
+use flow::FlowView;
+use layout::LayoutView;
+use matrix_view::MatrixView;
+use selection::Selection;
+use spanning_tree::SpanningTree;
+use topological::TopologicalView;
+use traversal::TraversalView;
+use egui_macroquad::{cfg, draw};
+use rust_graph::{DijkstraGraph, EditHistory, geometry::is_point_in_rectangle};
 use macroquad::{prelude::*, telemetry::disable, miniquad::conf::Icon};
 // use std::{fs::File, io::Write};
 // use image;
@@ -41,7 +64,7 @@ fn window_configuration() -> Conf
     window_width: 1290,
     window_height: 720,
     fullscreen: false,
-    window_resizable: false,
+    window_resizable: true,
     icon: Some(Icon
       {
         small: small_icon::give(),
@@ -58,10 +81,16 @@ pub(crate) enum Mode {
   Point,
   Line,
   Path,
+  Tree,
+  Traverse,
+  Components,
+  Order,
+  Flow,
 }
 
 pub(crate) const VERSION: Option<&str> = option_env!("CARGO_PKG_VERSION");
 pub(crate) const AUTHORS: Option<&str> = option_env!("CARGO_PKG_AUTHORS");
+/// The width the panel starts with, it can be made up to twice as wide
 pub(crate) const UI_WIDTH: f32 = 200.;
 pub(crate) const PADDING: u8 = 3;
 pub(crate) const BG_COLOR: u32 = 0x400080;
@@ -69,66 +98,163 @@ pub(crate) const PATH_COLOR: u32 = 0x00ff00;
 pub(crate) const LINE_COLOR: u32 = 0x00c0c0;
 pub(crate) const POINT_COLOR: u32 = 0xff8000;
 pub(crate) const LINE_LENGTH_COLOR: u32 = 0xc09ac0;
-pub(crate) const  UI_SPACING: f32 = 229.;
+pub(crate) const CURRENT_COLOR: u32 = 0xffe000;
+pub(crate) const FRONTIER_COLOR: u32 = 0x00a0ff;
+pub(crate) const VISITED_COLOR: u32 = 0x808080;
+pub(crate) const TREE_COLOR: u32 = 0xff60c0;
+pub(crate) const NEGATIVE_CYCLE_COLOR: u32 = 0xff4040;
+pub(crate) const BACK_LINE_COLOR: u32 = 0xff4040;
+pub(crate) const FORWARD_LINE_COLOR: u32 = 0xffe000;
+pub(crate) const CROSS_LINE_COLOR: u32 = 0x00a0ff;
+pub(crate) const CYCLE_COLOR: u32 = 0xff4040;
+pub(crate) const CRITICAL_PATH_COLOR: u32 = 0xffe000;
+pub(crate) const CUT_COLOR: u32 = 0xff4040;
+pub(crate) const HOVERED_LINE_COLOR: u32 = 0xff00ff;
+pub(crate) const SELECTION_COLOR: u32 = 0xffffff;
+/// How far the mouse can be away from a line to hover over it
+pub(crate) const LINE_HOVER_DISTANCE: f32 = 6.;
 
 #[macroquad::main(window_configuration)]
 async fn main()
 {
   disable();
+  // Seeds macroquad's generator, the panel draws the seeds for the random tie-break from it. The tie-break itself
+  // uses its own generator with the picked seed.
+  rand::srand(miniquad::date::now() as u64);
 
   let mut graph = DijkstraGraph::new();
+  // Every edit of the graph goes through here so that it can be undone
+  let mut history = EditHistory::new();
   // This is the id of the point that the mouse is currently hovering over
   let mut hovered_point_id: Option<usize> = None;
-  // This is the id of the point the mouse is currently hovering over and mouse 1 is pressed
+  // This is the id of the point a line is drawn from in line mode
   let mut selected_point_id: Option<usize> = None;
+  // These are the points selected in move mode
+  let mut selection = Selection::default();
 
   let mut mode = Mode::Move;
 
   let mut angle: f32 = 0.436;
   let mut arrow_head_length: f32 = 20.;
   let mut radius: f32 = 13.;
-  let mut line_length: u16 = 1;
+  let mut line_length: i32 = 1;
   let mut path_thickness: f32 = 2.;
   let mut base_point: f32 = 15.;
 
   let mut hexagons: bool = false;
 
+  let mut file_path = String::from("graph.json");
+  // Tells the user whether saving or opening a file worked
+  let mut file_message = String::new();
+
+  let mut animation = Animation::default();
+  let mut all_paths = AllPaths::default();
+  let mut spanning_tree = SpanningTree::default();
+  let mut comparison = Comparison::default();
+  let mut matrix_view = MatrixView::default();
+  let mut traversal_view = TraversalView::default();
+  let mut components_view = ComponentsView::default();
+  let mut topological_view = TopologicalView::default();
+  let mut flow_view = FlowView::default();
+  let mut layout_view = LayoutView::default();
+  let mut camera = Camera::default();
+  // The width of the part of the window left of the panel, it is measured while painting the panel
+  let mut canvas_width = screen_width() - UI_WIDTH;
+  // Where the mouse has been during the last frame while the canvas is dragged around with the middle mouse button
+  let mut panning_from: Option<(f32, f32)> = None;
+
   loop
   {
     clear_background(Color::from_hex(BG_COLOR));
 
-    // Delete or backspace clears the graph of all points and lines
-    if is_key_pressed(KeyCode::Backspace) || is_key_pressed(KeyCode::Delete)
-    { graph.clear(); }
+    // Keyboard shortcuts are ignored while typing into a text field, the mouse wheel while it is over a window
+    let mut is_typing = false;
+    let mut is_over_ui = false;
+    cfg(|egui_context|
+    {
+      is_typing = egui_context.wants_keyboard_input();
+      is_over_ui = egui_context.is_pointer_over_area();
+    });
+
+    // A selection doesn't outlive the points and lines it has been made for
+    selection.update(&graph);
+
+    // Delete or backspace removes the selected points and their lines
+    if !is_typing && (is_key_pressed(KeyCode::Backspace) || is_key_pressed(KeyCode::Delete))
+    { selection.remove(&mut graph, &mut history); }
+
+    // Ctrl+Z undoes the last edit, Ctrl+Shift+Z redoes it
+    if !is_typing && is_key_pressed(KeyCode::Z) && (is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl))
+    {
+      let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+      let changed = if shift { history.redo(&mut graph) } else { history.undo(&mut graph) };
+      if changed
+      {
+        animation.stop();
+        selected_point_id = None;
+      }
+    }
+
+    // --- CAMERA ---
+    let (_, wheel) = mouse_wheel();
+    if !is_over_ui && wheel != 0.
+    { camera.zoom_at(mouse_position(), ZOOM_STEP.powf(wheel.signum())); }
+
+    if !is_over_ui && is_mouse_button_pressed(MouseButton::Middle)
+    { panning_from = Some(mouse_position()); }
+    if let Some((x, y)) = panning_from
+    {
+      camera.pan((mouse_position().0 - x, mouse_position().1 - y));
+      panning_from = is_mouse_button_down(MouseButton::Middle).then(mouse_position);
+    }
 
     // --- INPUT ---
-    if utils::is_point_in_rectangle(
+    // This is the line the mouse is currently hovering over in line mode, as `(from, to)`
+    let mut hovered_line: Option<(usize, usize)> = None;
+    if is_point_in_rectangle(
       mouse_position().0,
       mouse_position().1,
       radius,
       radius,
-      screen_width() - (UI_WIDTH + (3_f32 * radius)),
+      canvas_width - (3_f32 * radius),
       screen_height() - (2_f32 * radius),
     )
     {
-      hovered_point_id = graph.find_hovered_point(mouse_position().0, mouse_position().1, radius);
+      // The points live in the world, the mouse on the screen
+      let mouse = camera.to_world(mouse_position());
+      hovered_point_id = graph.find_hovered_point(mouse.0, mouse.1, radius);
+      // Points take precedence over the lines leading to them
+      hovered_line = match (mode, hovered_point_id)
+      {
+        (Mode::Line, None) => graph.find_hovered_line(mouse.0, mouse.1, radius, LINE_HOVER_DISTANCE / camera.zoom),
+        _ => None,
+      };
       utils::handle_mouse_input(
         is_mouse_button_pressed(MouseButton::Left),
         is_mouse_button_down(MouseButton::Left),
         is_mouse_button_released(MouseButton::Left),
         is_mouse_button_pressed(MouseButton::Right),
+        is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift),
+        mouse,
         &mode,
         &mut graph,
-        &mut hovered_point_id,
+        &mut history,
+        &hovered_point_id,
         &mut selected_point_id,
+        &mut selection,
+        &hovered_line,
         &mut line_length
       );
     }
+    // Letting go of the mouse outside of the canvas still drops the selection or the rubber band
+    if !is_mouse_button_down(MouseButton::Left)
+    { selection.release(&graph, &mut history); }
 
     // --- GUI ---
     ui::paint_ui(
       &mut mode,
       &mut graph,
+      &mut history,
       &mut radius,
       &mut angle,
       &mut arrow_head_length,
@@ -137,24 +263,77 @@ async fn main()
       &mut selected_point_id,
       &mut line_length,
       &mut hexagons,
+      &mut file_path,
+      &mut file_message,
+      &mut animation,
+      &mut all_paths,
+      &mut spanning_tree,
+      &comparison,
+      &mut matrix_view,
+      &mut traversal_view,
+      &mut components_view,
+      &mut topological_view,
+      &mut flow_view,
+      &mut layout_view,
+      &mut camera,
+      &mut canvas_width,
     );
 
+    animation.update(&mut graph, get_frame_time());
+    // The simulation leaves the points that are being moved where the mouse has put them
+    layout_view.update(&mut graph, &selection.dragged(), get_frame_time());
+    // Any change to the path invalidates the list of all shortest paths
+    if graph.get_path().is_none()
+    { all_paths.clear(); }
+    if mode == Mode::Tree
+    { spanning_tree.update(&graph); }
+    if mode == Mode::Path
+    { comparison.update(&graph); }
+    if mode == Mode::Traverse
+    { traversal_view.update(&graph); }
+    if mode == Mode::Components
+    { components_view.update(&graph); }
+    if mode == Mode::Order
+    { topological_view.update(&graph); }
+    if mode == Mode::Flow
+    { flow_view.update(&graph); }
+    matrix_view.update(&graph);
+
     // ! dbg
-    if is_key_pressed(KeyCode::P)
+    if !is_typing && is_key_pressed(KeyCode::P)
     {
       graph.print_graph_data();
     }
 
+    // The collapsed components are painted instead of the graph, their ids don't match the points under the mouse
+    let components_mode = mode == Mode::Components;
+    if components_mode && components_view.collapsed
+    { hovered_point_id = None; }
+
+    set_camera(&camera.macroquad_camera());
     utils::paint_graph(
-      &graph, &radius,
+      if components_mode { components_view.painted(&graph) } else { &graph },
+      &radius,
       &path_thickness,
       &angle,
       &base_point,
       &arrow_head_length,
       &mut hovered_point_id,
       &selected_point_id,
+      // The selected ids don't match the points of the condensation either
+      (!(components_mode && components_view.collapsed)).then_some(&selection),
+      &hovered_line,
       &hexagons,
+      &animation,
+      &all_paths,
+      (mode == Mode::Tree).then_some(&spanning_tree.forest),
+      (mode == Mode::Traverse).then_some(&traversal_view),
+      components_mode.then_some(&components_view),
+      (mode == Mode::Order).then_some(&topological_view),
+      (mode == Mode::Flow).then_some(&flow_view),
     );
+    // The UI is painted in screen coordinates
+    set_default_camera();
 
     draw();
 
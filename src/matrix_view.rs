@@ -0,0 +1,38 @@
+use rust_graph::{DijkstraGraph, Distance, DistanceMatrix};
+
+/// Larger graphs make the matrix too slow to compute and too big to read
+pub(crate) const MAX_MATRIX_POINTS: usize = 40;
+
+/// The window that shows the distances between all pairs of points
+#[derive(Default)]
+pub(crate) struct MatrixView
+{
+  pub(crate) open: bool,
+  /// `None` if the graph has too many points
+  pub(crate) matrix: Option<DistanceMatrix>,
+  /// The revision of the graph the matrix has been computed for
+  computed_for: Option<u64>,
+}
+
+impl MatrixView
+{
+  /// Recomputes the matrix while the window is open, but only if the graph has changed since the last time
+  pub(crate) fn update(&mut self, graph: &DijkstraGraph)
+  {
+    if !self.open || self.computed_for == Some(graph.revision()) { return; }
+
+    self.matrix = (graph.points().len() <= MAX_MATRIX_POINTS).then(|| graph.all_pairs_shortest_paths());
+    self.computed_for = Some(graph.revision());
+  }
+}
+
+/// The text of a single cell of the matrix
+pub(crate) fn describe_distance(distance: Distance) -> String
+{
+  return match distance
+  {
+    Distance::Unreachable => String::from("∞"),
+    Distance::Finite(distance) => distance.to_string(),
+    Distance::NegativeInfinity => String::from("-∞"),
+  };
+}
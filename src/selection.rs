@@ -0,0 +1,89 @@
+use rust_graph::{DijkstraGraph, EditHistory};
+use std::collections::BTreeSet;
+
+/// The points selected in move mode, they are moved and deleted together
+#[derive(Default)]
+pub(crate) struct Selection
+{
+  /// The ids of the selected points
+  pub(crate) ids: BTreeSet<usize>,
+  /// The corner the rubber band has been started at and the corner under the mouse, in world coordinates
+  pub(crate) rubber_band: Option<((f32, f32), (f32, f32))>,
+  /// Where the mouse has been during the last frame while the selection is moved around
+  grabbed_at: Option<(f32, f32)>,
+  /// The revision of the graph the ids belong to
+  revision: Option<u64>,
+}
+
+impl Selection
+{
+  /// Forgets the selection once points or lines have changed, its ids could belong to other points by then
+  pub(crate) fn update(&mut self, graph: &DijkstraGraph)
+  {
+    if self.revision == Some(graph.revision()) { return; }
+    self.revision = Some(graph.revision());
+    self.ids.clear();
+  }
+
+  /// The points that are being moved around with the mouse
+  pub(crate) fn dragged(&self) -> Vec<usize>
+  { return if self.grabbed_at.is_some() { self.ids.iter().copied().collect() } else { vec![] }; }
+
+  /// Adds a point to the selection or takes it out again
+  pub(crate) fn toggle(&mut self, id: usize)
+  {
+    if !self.ids.remove(&id)
+    { self.ids.insert(id); }
+  }
+
+  /// Grabs the selection to move it around, grabbing a point outside of it selects only that point
+  pub(crate) fn grab(&mut self, graph: &DijkstraGraph, history: &mut EditHistory, id: usize, mouse: (f32, f32))
+  {
+    if !self.ids.contains(&id)
+    { self.ids = BTreeSet::from([id]); }
+    self.grabbed_at = Some(mouse);
+    history.start_move(graph, &self.ids.iter().copied().collect::<Vec<usize>>());
+  }
+
+  /// Starts a rubber band at the mouse, unless points are added to the selection it starts over
+  pub(crate) fn start_rubber_band(&mut self, mouse: (f32, f32), adding: bool)
+  {
+    if !adding
+    { self.ids.clear(); }
+    self.rubber_band = Some((mouse, mouse));
+  }
+
+  /// Moves the grabbed selection along with the mouse or drags the rubber band open
+  pub(crate) fn drag(&mut self, graph: &mut DijkstraGraph, mouse: (f32, f32))
+  {
+    if let Some((x, y)) = self.grabbed_at
+    {
+      self.ids.iter()
+        .for_each(|id|
+        {
+          let Some(point) = graph.get_mut(*id) else { return; };
+          point.x += mouse.0 - x;
+          point.y += mouse.1 - y;
+        });
+      self.grabbed_at = Some(mouse);
+    }
+    else if let Some((corner, _)) = self.rubber_band
+    { self.rubber_band = Some((corner, mouse)); }
+  }
+
+  /// Records the move of the selection or selects the points inside the rubber band
+  pub(crate) fn release(&mut self, graph: &DijkstraGraph, history: &mut EditHistory)
+  {
+    if self.grabbed_at.take().is_some()
+    { history.end_move(graph); }
+    if let Some((corner, opposite_corner)) = self.rubber_band.take()
+    { self.ids.extend(graph.points_in_rectangle(corner, opposite_corner)); }
+  }
+
+  /// Removes the selected points and all lines leading to them as a single edit
+  pub(crate) fn remove(&mut self, graph: &mut DijkstraGraph, history: &mut EditHistory)
+  {
+    history.remove_points(graph, &self.ids.iter().copied().collect::<Vec<usize>>());
+    self.ids.clear();
+  }
+}
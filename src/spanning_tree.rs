@@ -0,0 +1,38 @@
+use rust_graph::{DijkstraGraph, SpanningForest, SpanningTreeAlgorithm};
+
+/// The minimum spanning forest drawn in tree mode
+#[derive(Default)]
+pub(crate) struct SpanningTree
+{
+  pub(crate) algorithm: SpanningTreeAlgorithm,
+  pub(crate) forest: SpanningForest,
+  /// The revision of the graph and the algorithm the forest has been computed for
+  computed_for: Option<(u64, SpanningTreeAlgorithm)>,
+}
+
+impl SpanningTree
+{
+  /// Recomputes the forest, but only if the graph or the algorithm has changed since the last time
+  pub(crate) fn update(&mut self, graph: &DijkstraGraph)
+  {
+    if self.computed_for == Some((graph.revision(), self.algorithm)) { return; }
+
+    self.forest = graph.minimum_spanning_forest(self.algorithm);
+    self.computed_for = Some((graph.revision(), self.algorithm));
+  }
+
+  /// A human readable summary of the forest
+  pub(crate) fn describe(&self) -> String
+  {
+    return match self.forest.trees
+    {
+      0 => String::from("The graph is empty."),
+      1 => format!("Total weight: {}", self.forest.total_weight),
+      trees => format!(
+        "The graph is disconnected, this is a spanning forest of {} trees.\nTotal weight: {}",
+        trees,
+        self.forest.total_weight,
+      ),
+    };
+  }
+}
@@ -0,0 +1,57 @@
+use rust_graph::{CriticalPath, DijkstraGraph, TopologicalOrder};
+
+/// The topological order and critical path shown in order mode, or the cycle that prevents them
+#[derive(Default)]
+pub(crate) struct TopologicalView
+{
+  /// `None` if the graph has a cycle
+  pub(crate) order: Option<TopologicalOrder>,
+  pub(crate) critical_path: Option<CriticalPath>,
+  pub(crate) cycle: Option<Vec<usize>>,
+  /// The revision of the graph the order has been computed for
+  computed_for: Option<u64>,
+}
+
+impl TopologicalView
+{
+  /// Orders the graph again, but only if it has changed since the last time
+  pub(crate) fn update(&mut self, graph: &DijkstraGraph)
+  {
+    if self.computed_for == Some(graph.revision()) { return; }
+
+    match graph.topological_order()
+    {
+      Ok(order) =>
+      {
+        self.order = Some(order);
+        self.critical_path = graph.critical_path().ok();
+        self.cycle = None;
+      },
+      Err(cycle) =>
+      {
+        self.order = None;
+        self.critical_path = None;
+        self.cycle = Some(cycle);
+      },
+    }
+    self.computed_for = Some(graph.revision());
+  }
+
+  /// The position of every point in the order, painted below it
+  pub(crate) fn labels(&self) -> Vec<(usize, String)>
+  {
+    let Some(order) = &self.order else { return vec![]; };
+
+    return order.order.iter().enumerate()
+      .map(|(position, id)| (*id, format!("#{}", position + 1)))
+      .collect();
+  }
+}
+
+/// Lists the ids of a cycle or path with arrows in between, a cycle ends where it started
+pub(crate) fn describe_ids(ids: &[usize], is_cycle: bool) -> String
+{
+  let mut ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+  if is_cycle && !ids.is_empty() { ids.push(ids[0].clone()); }
+  return ids.join(" → ");
+}
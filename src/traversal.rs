@@ -0,0 +1,40 @@
+use rust_graph::{DijkstraGraph, Traversal, TraversalKind};
+
+/// The breadth-first or depth-first traversal from the start, drawn in traversal mode
+#[derive(Default)]
+pub(crate) struct TraversalView
+{
+  pub(crate) kind: TraversalKind,
+  /// `None` while there is no start
+  pub(crate) traversal: Option<Traversal>,
+  /// The revision of the graph and the kind the traversal has been computed for
+  computed_for: Option<(u64, TraversalKind)>,
+}
+
+impl TraversalView
+{
+  /// Traverses the graph again, but only if the graph or the kind has changed since the last time
+  pub(crate) fn update(&mut self, graph: &DijkstraGraph)
+  {
+    if self.computed_for == Some((graph.revision(), self.kind)) { return; }
+
+    self.traversal = graph.start().map(|start| graph.traverse(start, self.kind));
+    self.computed_for = Some((graph.revision(), self.kind));
+  }
+
+  /// The label painted below every reached point: its level for breadth-first traversals, its discovery and finish
+  /// order for depth-first ones
+  pub(crate) fn labels(&self) -> Vec<(usize, String)>
+  {
+    let Some(traversal) = &self.traversal else { return vec![]; };
+
+    return traversal.discovery_order.iter()
+      .map(|id| match self.kind
+      {
+        TraversalKind::BreadthFirst => (*id, format!("L{}", traversal.levels[*id].unwrap())),
+        TraversalKind::DepthFirst =>
+          (*id, format!("{}/{}", traversal.discovered[*id].unwrap() + 1, traversal.finished[*id].unwrap() + 1)),
+      })
+      .collect();
+  }
+}
@@ -1,28 +1,73 @@
-use std::ops::Mul;
-
-use crate::{graph::DijkstraGraph, Mode, VERSION, AUTHORS, UI_SPACING};
+use macroquad::{rand, window::screen_height};
+use rust_graph::{DijkstraGraph, EditHistory, Layering, PathAlgorithm, SpanningTreeAlgorithm, TieBreak, TraversalKind};
+use crate::{all_paths::{AllPaths, MAX_PATHS}, animation::Animation, comparison::Comparison, spanning_tree::SpanningTree};
+use crate::{components::ComponentsView, matrix_view::{describe_distance, MatrixView, MAX_MATRIX_POINTS}, traversal::TraversalView};
+use crate::{camera::Camera, flow::FlowView, layout::LayoutView, topological::{describe_ids, TopologicalView}};
+use crate::{Mode, VERSION, AUTHORS, UI_WIDTH, PATH_COLOR, BACK_LINE_COLOR, FORWARD_LINE_COLOR, CROSS_LINE_COLOR};
+use crate::{CYCLE_COLOR, CRITICAL_PATH_COLOR, CUT_COLOR, CURRENT_COLOR};
 
+use std::ops::RangeInclusive;
 use egui_macroquad::{
-  egui::{epaint::Shadow, Align2, Rounding, Slider, Vec2, Visuals, Window, Color32, Stroke},
+  egui::{epaint::Shadow, Rounding, Slider, Vec2, Visuals, Window, Color32, Stroke, DragValue, ComboBox, Grid, ScrollArea, Context, SidePanel},
   ui,
 };
 
+/// Converts one of the colours used for painting the graph
+fn hex_color(color: u32) -> Color32
+{ return Color32::from_rgb((color >> 16) as u8, (color >> 8) as u8, color as u8); }
+
+/// Lists the given ids, large graphs would fill the whole panel so only the first 50 are shown
+fn list_ids(ids: &[usize]) -> String
+{
+  let mut list = ids.iter().take(50).map(|id| id.to_string()).collect::<Vec<String>>().join(", ");
+  if ids.len() > 50 { list.push_str(", …"); }
+  return list;
+}
+
+/// The scales of the A* heuristic that can be picked
+const A_STAR_SCALES: RangeInclusive<f32> = 0.001..=1.0;
+
+/// Graphviz files are recognised by their extension, everything else is treated as JSON
+fn is_dot_file(path: &str) -> bool
+{ return path.ends_with(".dot") || path.ends_with(".gv"); }
+
 // TODO: light theme
 // TODO: make colours editable
 
 pub(crate) fn paint_ui(
   mode: &mut Mode,
   graph: &mut DijkstraGraph,
+  history: &mut EditHistory,
   radius: &mut f32,
   angle: &mut f32,
   arrow_head_length: &mut f32,
   path_thickness: &mut f32,
   base_point: &mut f32,
   selected_point_id: &mut Option<usize>,
-  line_length: &mut u16,
+  line_length: &mut i32,
   hexagons: &mut bool,
+  file_path: &mut String,
+  file_message: &mut String,
+  animation: &mut Animation,
+  all_paths: &mut AllPaths,
+  spanning_tree: &mut SpanningTree,
+  comparison: &Comparison,
+  matrix_view: &mut MatrixView,
+  traversal_view: &mut TraversalView,
+  components_view: &mut ComponentsView,
+  topological_view: &mut TopologicalView,
+  flow_view: &mut FlowView,
+  layout_view: &mut LayoutView,
+  camera: &mut Camera,
+  canvas_width: &mut f32,
 )
 {
+  // The layouts spread the points over the part of the world that is visible on the canvas, away from its edges
+  let margin = 3. * *radius;
+  let (left, top) = camera.to_world((margin, margin));
+  let (right, bottom) = camera.to_world((*canvas_width - margin, screen_height() - margin));
+  let (origin, size) = ((left, top), (right - left, bottom - top));
+
   ui(|egui_context| {
     egui_context.set_visuals(Visuals
     {
@@ -35,6 +80,7 @@ pub(crate) fn paint_ui(
         se: 0.,
       },
       window_fill: Color32::from_rgb(32, 0, 64),
+      panel_fill: Color32::from_rgb(32, 0, 64),
       window_stroke: Stroke::new(2., Color32::from_rgb(0, 192, 192)),
       override_text_color: Some(Color32::from_rgb(255, 210, 255)),
       // widgets: Widgets::style(&self, response),
@@ -42,14 +88,11 @@ pub(crate) fn paint_ui(
     });
 
     // egui ❤ macroquad
-    Window::new("Rust Graph Visualiser")
-      .anchor(Align2::RIGHT_TOP, Vec2::new(-1.5, 10.))
-      .constrain(true)
-      .collapsible(false)
-      .movable(false)
-      .resizable(false)
-      .fixed_size(Vec2::new(200., 0.))
-      .show(egui_context, |ui|
+    // The panel stays docked to the right, everything that doesn't fit into the height of the window can be scrolled to
+    let panel = SidePanel::right("panel")
+      .default_width(UI_WIDTH)
+      .width_range(UI_WIDTH..=2. * UI_WIDTH)
+      .show(egui_context, |ui| ScrollArea::vertical().show(ui, |ui|
       {
         ui.style_mut().visuals.widgets.inactive.weak_bg_fill = Color32::from_rgb(0, 64, 64);
         ui.style_mut().visuals.widgets.inactive.bg_fill = Color32::from_rgb(0, 64, 64);
@@ -58,25 +101,42 @@ pub(crate) fn paint_ui(
         ui.style_mut().visuals.widgets.active.weak_bg_fill = Color32::from_rgb(0, 192, 192);
         ui.style_mut().visuals.widgets.active.bg_fill = Color32::from_rgb(0, 192, 192);
 
+        ui.heading("Rust Graph Visualiser");
+        ui.separator();
+
         ui.label("Select a mode:");
-        ui.horizontal(|ui|
+        let previous_mode = *mode;
+        ui.horizontal_wrapped(|ui|
         {
           ui.selectable_value(mode, Mode::Move, "Move");
           ui.selectable_value(mode, Mode::Line, "Line");
           ui.selectable_value(mode, Mode::Point, "Point");
           ui.selectable_value(mode, Mode::Path, "Path");
+          ui.selectable_value(mode, Mode::Tree, "Tree");
+          ui.selectable_value(mode, Mode::Traverse, "Traverse");
+          ui.selectable_value(mode, Mode::Components, "Components");
+          ui.selectable_value(mode, Mode::Order, "Order");
+          ui.selectable_value(mode, Mode::Flow, "Flow");
         });
+        // The animation only makes sense in path mode
+        if *mode != previous_mode
+        { animation.stop(); }
 
         ui.separator();
 
-        // The newlines are a hack to make all text fill up the same amount of vertical space
+        // Every hint starts on a line of its own
         match (&mode, selected_point_id.is_some())
         {
-          (Mode::Move, _) => ui.label("• Left click on a point to select it.\n• Hold left click to move it around."),
-          (Mode::Line, false) => ui.label("• Left click on a point to select it."),
+          (Mode::Move, _) => ui.label("• Left click on a point to select it.\n• Shift + left click to select more points.\n• Drag over empty space to select all points in a box.\n• Hold left click to move the selection around.\n• Delete or backspace removes the selection."),
+          (Mode::Line, false) => ui.label("• Left click on a point to select it.\n• Right click on a line to delete it."),
           (Mode::Line, true) => ui.label("• Left click on another point to create a new line.\n• Right click on another point to delete an existing line."),
           (Mode::Point, _) => ui.label("• Left click somewhere to create a point.\n• Right click on a point to delete it."),
-          (Mode::Path, _) => ui.label("• Left click on a point to set the start.\n• Right click on a point to set the end.")
+          (Mode::Path, _) => ui.label("• Left click on a point to set the start.\n• Right click on a point to set the end."),
+          (Mode::Tree, _) => ui.label("• The minimum spanning tree is highlighted.\n• The direction of the lines is ignored."),
+          (Mode::Traverse, _) => ui.label("• Left click on a point to start the traversal there."),
+          (Mode::Components, _) => ui.label("• Every strongly connected component has its own colour."),
+          (Mode::Flow, _) => ui.label("• Left click on a point to set the source.\n• Right click on a point to set the sink."),
+          (Mode::Order, _) => ui.label("• The position of every point in the topological order is shown below it."),
         };
 
         match &mode
@@ -85,7 +145,7 @@ pub(crate) fn paint_ui(
           {
             ui.separator();
             ui.label("Line length:");
-            ui.add(Slider::new(line_length, 1..=255).logarithmic(true));
+            ui.add(Slider::new(line_length, -255..=255).logarithmic(true));
           }
           Mode::Path =>
           {
@@ -95,11 +155,148 @@ pub(crate) fn paint_ui(
               ui.add_enabled_ui(graph.start().is_some() && graph.end().is_some(), |ui|
               {
                 if ui.button("Find shortest path").clicked()
-                { graph.find_shortest_path(); }
+                {
+                  animation.stop();
+                  graph.find_shortest_path();
+                  all_paths.find(graph);
+                }
               });
               if ui.button("Clear path").clicked()
-              { graph.clear_path(); }
+              {
+                animation.stop();
+                graph.clear_path();
+              }
+            });
+
+            let mut path_algorithm = graph.path_algorithm();
+            ui.horizontal(|ui|
+            {
+              ui.label("Algorithm:");
+              ui.selectable_value(&mut path_algorithm, PathAlgorithm::Dijkstra, "Dijkstra");
+              if ui.selectable_label(matches!(path_algorithm, PathAlgorithm::AStar { .. }), "A*").clicked()
+                && !matches!(path_algorithm, PathAlgorithm::AStar { .. })
+              {
+                let scale = graph.consistent_scale().unwrap_or(0.01).clamp(*A_STAR_SCALES.start(), *A_STAR_SCALES.end());
+                path_algorithm = PathAlgorithm::AStar { scale };
+              }
+              ui.selectable_value(&mut path_algorithm, PathAlgorithm::BellmanFord, "Bellman-Ford");
             });
+            if path_algorithm != PathAlgorithm::BellmanFord && graph.has_negative_lines()
+            {
+              ui.colored_label(
+                Color32::from_rgb(255, 192, 0),
+                "⚠ Some lines are negative. Dijkstra's algorithm and A* treat the distance of a visited point as final, so they might miss the shortest path.",
+              );
+            }
+            if graph.negative_cycle().is_some()
+            { ui.colored_label(Color32::from_rgb(255, 64, 64), "The highlighted cycle has a negative length, going around it once more always leads to a shorter path. There is no shortest path."); }
+            if let PathAlgorithm::AStar { scale } = &mut path_algorithm
+            {
+              ui.horizontal(|ui|
+              {
+                ui.label("Scale:");
+                ui.add(Slider::new(scale, A_STAR_SCALES).logarithmic(true));
+                // The largest scale for which the heuristic is guaranteed to be admissible
+                let consistent_scale = graph.consistent_scale();
+                ui.add_enabled_ui(consistent_scale.is_some(), |ui|
+                {
+                  if let (true, Some(consistent_scale)) = (ui.button("Fit").clicked(), consistent_scale)
+                  { *scale = consistent_scale.clamp(*A_STAR_SCALES.start(), *A_STAR_SCALES.end()); }
+                });
+              });
+              if let Some((dijkstra, a_star)) = comparison.expanded
+              { ui.label(format!("Expanded points: {} with Dijkstra, {} with A*", dijkstra, a_star)); }
+              if let Some(warning) = comparison.warning()
+              { ui.colored_label(Color32::from_rgb(255, 192, 0), warning); }
+            }
+            if path_algorithm != graph.path_algorithm()
+            {
+              animation.stop();
+              graph.set_path_algorithm(path_algorithm);
+            }
+
+            let mut tie_break = graph.tie_break();
+            ui.horizontal(|ui|
+            {
+              ui.label("Equal distances:");
+              ComboBox::from_id_source("tie_break")
+                .selected_text(match tie_break
+                {
+                  TieBreak::LowestId => "Lowest id",
+                  TieBreak::FewestHops => "Fewest hops",
+                  TieBreak::SeededRandom(_) => "Random",
+                })
+                .show_ui(ui, |ui|
+                {
+                  let seed = match tie_break
+                  {
+                    TieBreak::SeededRandom(seed) => seed,
+                    _ => rand::rand() as u64,
+                  };
+                  ui.selectable_value(&mut tie_break, TieBreak::LowestId, "Lowest id");
+                  ui.selectable_value(&mut tie_break, TieBreak::FewestHops, "Fewest hops");
+                  ui.selectable_value(&mut tie_break, TieBreak::SeededRandom(seed), "Random");
+                });
+            });
+            if let TieBreak::SeededRandom(seed) = &mut tie_break
+            {
+              ui.horizontal(|ui|
+              {
+                ui.label("Seed:");
+                ui.add(DragValue::new(seed));
+                if ui.button("New seed").clicked()
+                { *seed = rand::rand() as u64; }
+              });
+            }
+            if tie_break != graph.tie_break()
+            {
+              animation.stop();
+              graph.set_tie_break(tie_break);
+            }
+
+            if !all_paths.paths.is_empty()
+            {
+              ui.separator();
+              ui.label(match all_paths.paths.len()
+              {
+                1 => String::from("There is 1 shortest path."),
+                MAX_PATHS => format!("There are at least {} shortest paths.", MAX_PATHS),
+                amount => format!("There are {} shortest paths.", amount),
+              });
+              ui.horizontal(|ui|
+              {
+                ui.add_enabled_ui(!all_paths.show_all && all_paths.paths.len() > 1, |ui|
+                {
+                  if ui.button("◀").clicked() { all_paths.previous(); }
+                  ui.label(format!("{} / {}", all_paths.index + 1, all_paths.paths.len()));
+                  if ui.button("▶").clicked() { all_paths.next(); }
+                });
+                ui.checkbox(&mut all_paths.show_all, "Show all");
+              });
+            }
+
+            ui.separator();
+            ui.label("Step by step:");
+            if graph.path_algorithm() == PathAlgorithm::BellmanFord
+            { ui.label("Bellman-Ford can't be run step by step."); }
+            else
+            {
+              ui.horizontal(|ui|
+              {
+                ui.add_enabled_ui(graph.start().is_some() && graph.end().is_some(), |ui|
+                {
+                  if ui.button("Step").clicked()
+                  {
+                    animation.playing = false;
+                    animation.step(graph);
+                  }
+                  if ui.button(if animation.playing { "Pause" } else { "Play" }).clicked()
+                  { animation.toggle_playing(); }
+                });
+                ui.add(Slider::new(&mut animation.steps_per_second, 0.5..=20.0).logarithmic(true).suffix("/s"));
+              });
+              ui.label(animation.describe());
+            }
             /*
             ui.horizontal(|ui|
             {
@@ -108,19 +305,130 @@ pub(crate) fn paint_ui(
             });
             */
           }
-          _ => ()
-        }
+          Mode::Tree =>
+          {
+            ui.separator();
+            ui.horizontal(|ui|
+            {
+              ui.label("Algorithm:");
+              ui.selectable_value(&mut spanning_tree.algorithm, SpanningTreeAlgorithm::Prim, "Prim");
+              ui.selectable_value(&mut spanning_tree.algorithm, SpanningTreeAlgorithm::Kruskal, "Kruskal");
+            });
+            ui.label(spanning_tree.describe());
+          }
+          Mode::Traverse =>
+          {
+            ui.separator();
+            ui.horizontal(|ui|
+            {
+              ui.selectable_value(&mut traversal_view.kind, TraversalKind::BreadthFirst, "Breadth-first");
+              ui.selectable_value(&mut traversal_view.kind, TraversalKind::DepthFirst, "Depth-first");
+            });
+            ui.horizontal_wrapped(|ui|
+            {
+              ui.label("Lines:");
+              ui.colored_label(hex_color(PATH_COLOR), "tree");
+              ui.colored_label(hex_color(BACK_LINE_COLOR), "back");
+              if traversal_view.kind == TraversalKind::DepthFirst
+              { ui.colored_label(hex_color(FORWARD_LINE_COLOR), "forward"); }
+              ui.colored_label(hex_color(CROSS_LINE_COLOR), "cross");
+            });
+            ui.label(match traversal_view.kind
+            {
+              TraversalKind::BreadthFirst => "The level of every point is shown below it.",
+              TraversalKind::DepthFirst => "The discovery/finish order of every point is shown below it.",
+            });
 
-        ui.separator();
+            if let Some(traversal) = &traversal_view.traversal
+            {
+              ui.label(format!("Visit order: {}", list_ids(&traversal.discovery_order)));
+              if traversal_view.kind == TraversalKind::DepthFirst
+              { ui.label(format!("Finish order: {}", list_ids(&traversal.finish_order))); }
+            }
+          }
+          Mode::Order =>
+          {
+            ui.separator();
+            if let Some(cycle) = &topological_view.cycle
+            {
+              ui.colored_label(
+                hex_color(CYCLE_COLOR),
+                format!("There is no order because of the highlighted cycle {}.", describe_ids(cycle, true)),
+              );
+              if graph.is_undirected()
+              { ui.label("Undirected lines lead both ways, so each of them is a cycle."); }
+            }
+            if let Some(order) = &topological_view.order
+            {
+              ui.label(format!("Order: {}", list_ids(&order.order)));
+              if ui.button("Arrange by rank")
+                .on_hover_text("Places every point in a column to the right of all points with lines to it")
+                .clicked()
+              {
+                if let Some(positions) = graph.layered_layout(Layering::Rank, origin, size)
+                { layout_view.arrange(graph, history, positions); }
+              }
+            }
+            if let Some(critical_path) = &topological_view.critical_path
+            {
+              ui.colored_label(
+                hex_color(CRITICAL_PATH_COLOR),
+                format!("Critical path: {}\nLength: {}", describe_ids(&critical_path.path, false), critical_path.length),
+              );
+            }
+          }
+          Mode::Components =>
+          {
+            ui.separator();
+            ui.checkbox(&mut components_view.collapsed, "Collapse components")
+              .on_hover_text("Shows every component as a single point, the lines between them never form a cycle");
+            ui.label(components_view.describe());
+          }
+          Mode::Flow =>
+          {
+            ui.separator();
+            if graph.has_negative_lines()
+            { ui.colored_label(Color32::from_rgb(255, 64, 64), "Lines of negative length can't carry any flow."); }
 
-        ui.add_space(match (&mode, selected_point_id)
-        {
-          (Mode::Move, _) => UI_SPACING,
-          (Mode::Line, None) => UI_SPACING-33.,
-          (Mode::Line, Some(_)) => UI_SPACING-75.,
-          (Mode::Point, _) => UI_SPACING-14.,
-          (Mode::Path, _) => UI_SPACING-58.
-        });
+            match &flow_view.flow
+            {
+              None => { ui.label("The length of every line is its capacity. Set a start and an end to send as much flow as possible between them."); },
+              Some(flow) =>
+              {
+                ui.label(format!("Maximum flow: {}", flow.value));
+
+                let mut stepping = flow_view.step.is_some();
+                if ui.checkbox(&mut stepping, "Step through augmenting paths").changed()
+                { flow_view.step = stepping.then_some(0); }
+
+                if let Some(step) = flow_view.step.as_mut()
+                {
+                  let steps = flow.augmenting_paths.len();
+                  ui.horizontal(|ui|
+                  {
+                    if ui.add_enabled_ui(*step > 0, |ui| ui.button("◀").clicked()).inner { *step -= 1; }
+                    ui.label(format!("{} / {}", step, steps));
+                    if ui.add_enabled_ui(*step < steps, |ui| ui.button("▶").clicked()).inner { *step += 1; }
+                  });
+                }
+
+                match flow_view.current_path()
+                {
+                  Some(augmenting_path) => { ui.colored_label(
+                    hex_color(CURRENT_COLOR),
+                    format!("+{} along {}", augmenting_path.amount, describe_ids(&augmenting_path.path, false)),
+                  ); },
+                  None if flow_view.step.is_none() => { ui.colored_label(
+                    hex_color(CUT_COLOR),
+                    "The red lines form a minimum cut, their capacities add up to the maximum flow.",
+                  ); },
+                  None => (),
+                }
+              },
+            }
+          }
+          _ => ()
+        }
 
         ui.separator();
 
@@ -128,14 +436,167 @@ pub(crate) fn paint_ui(
         ui.horizontal(|ui|
         {
           if ui.button("Small").clicked()
-          { graph.insert_small_graph(); }
+          { history.replace(graph, |graph| graph.insert_small_graph()); }
           if ui.button("Medium").clicked()
-          { graph.insert_medium_graph(); }
+          { history.replace(graph, |graph| graph.insert_medium_graph()); }
           if ui.button("Large").clicked()
-          { graph.insert_large_graph(); }
+          { history.replace(graph, |graph| graph.insert_large_graph()); }
           if ui.button("Clear").clicked()
-          { graph.clear(); }
+          { history.replace(graph, |graph| graph.clear()); }
+        });
+
+        ui.horizontal(|ui|
+        {
+          let undo = history.next_undo().map(|edit| format!("Undo: {} (Ctrl+Z)", edit.describe()));
+          let redo = history.next_redo().map(|edit| format!("Redo: {} (Ctrl+Shift+Z)", edit.describe()));
+          let mut changed = false;
+          ui.add_enabled_ui(undo.is_some(), |ui|
+          {
+            if ui.button("⟲ Undo").on_hover_text(undo.unwrap_or_default()).clicked()
+            { changed = history.undo(graph); }
+          });
+          ui.add_enabled_ui(redo.is_some(), |ui|
+          {
+            if ui.button("⟳ Redo").on_hover_text(redo.unwrap_or_default()).clicked()
+            { changed = history.redo(graph); }
+          });
+          if changed
+          {
+            animation.stop();
+            *selected_point_id = None;
+          }
+        });
+
+        ui.separator();
+
+        ui.label("Layout:");
+        ui.horizontal(|ui|
+        {
+          if ui.button("Relayout")
+            .on_hover_text("Lets the points push each other away while the lines pull them together")
+            .clicked()
+          { layout_view.relayout(graph, history); }
+          ui.checkbox(&mut layout_view.simulating, "Simulate")
+            .on_hover_text("Keeps the layout running, a point that is moved around stays where it is held");
+        });
+        ui.horizontal(|ui|
+        {
+          if ui.button("Circle").on_hover_text("Places the points on a circle ordered by id").clicked()
+          {
+            let positions = graph.circle_layout(origin, size);
+            layout_view.arrange(graph, history, positions);
+          }
+          if ui.button("Grid").on_hover_text("Places the points on a grid ordered by id").clicked()
+          {
+            let positions = graph.grid_layout(origin, size);
+            layout_view.arrange(graph, history, positions);
+          }
+        });
+        ui.horizontal(|ui|
+        {
+          ui.label("Layers:");
+          ui.add_enabled_ui(graph.start().is_some(), |ui|
+          {
+            if ui.button("By depth").on_hover_text("Places the points in columns by their distance in lines from the start").clicked()
+            {
+              if let Some(positions) = graph.layered_layout(Layering::Depth, origin, size)
+              { layout_view.arrange(graph, history, positions); }
+            }
+          });
+          if ui.button("By rank").on_hover_text("Places every point in a column to the right of all points with lines to it").clicked()
+          {
+            if let Some(positions) = graph.layered_layout(Layering::Rank, origin, size)
+            { layout_view.arrange(graph, history, positions); }
+          }
+        });
+        ui.horizontal(|ui|
+        {
+          ui.label("Spring length:");
+          ui.add(Slider::new(&mut layout_view.layout.spring_length, 30.0..=300.0));
+        });
+        ui.checkbox(&mut layout_view.layout.weighted, "Longer lines get longer springs");
+
+        ui.separator();
+
+        ui.horizontal(|ui|
+        {
+          let mut limited = graph.max_points().is_some();
+          if ui.checkbox(&mut limited, "Node limit:").changed()
+          { graph.set_max_points(if limited { Some(graph.size().max(100)) } else { None }); }
+
+          match graph.max_points()
+          {
+            Some(mut max_points) =>
+            {
+              if ui.add(DragValue::new(&mut max_points).clamp_range(1..=100_000)).changed()
+              { graph.set_max_points(Some(max_points)); }
+              ui.label(format!("({} used)", graph.size()));
+            }
+            None => { ui.label(format!("none ({} used)", graph.size())); }
+          }
+        });
+
+        ui.horizontal(|ui|
+        {
+          let mut undirected = graph.is_undirected();
+          if ui.checkbox(&mut undirected, "Undirected")
+            .on_hover_text("Every line leads both ways with one shared length")
+            .changed()
+          { history.replace(graph, |graph| graph.set_undirected(undirected)); }
+          ui.checkbox(&mut matrix_view.open, "Distance matrix");
+        });
+
+        ui.horizontal(|ui|
+        {
+          ui.label(format!("Zoom: {:.0}%", camera.zoom * 100.))
+            .on_hover_text("Scroll to zoom, drag with the middle mouse button to move around");
+          if ui.button("Fit to view").clicked()
+          { camera.fit(graph, *canvas_width, screen_height(), 3. * *radius); }
+          if ui.button("Reset").clicked()
+          { *camera = Camera::default(); }
+        });
+
+        ui.separator();
+
+        ui.label("Graph file (.json or .dot):");
+        ui.text_edit_singleline(file_path);
+        ui.horizontal(|ui|
+        {
+          if ui.button("Save").clicked()
+          {
+            let result = if is_dot_file(file_path)
+            { graph.save_dot(&file_path).map_err(|error| error.to_string()) }
+            else
+            { graph.save(&file_path).map_err(|error| error.to_string()) };
+
+            *file_message = match result
+            {
+              Ok(()) => format!("Saved to {}", file_path),
+              Err(error) => error,
+            };
+          }
+          if ui.button("Open").clicked()
+          {
+            let result = if is_dot_file(file_path)
+            { DijkstraGraph::load_dot(&file_path).map_err(|error| error.to_string()) }
+            else
+            { DijkstraGraph::load(&file_path).map_err(|error| error.to_string()) };
+
+            *file_message = match result
+            {
+              Ok(loaded_graph) =>
+              {
+                // Only the points and lines are taken over, the node limit is a setting of the app, not of the file
+                history.replace(graph, |graph| *graph = loaded_graph);
+                *selected_point_id = None;
+                format!("Opened {}", file_path)
+              },
+              Err(error) => error,
+            };
+          }
         });
+        if !file_message.is_empty()
+        { ui.label(file_message.as_str()); }
 
         ui.separator();
         ui.heading("✨Style✨");
@@ -143,7 +604,7 @@ pub(crate) fn paint_ui(
         ui.checkbox(hexagons, "Hexagons");
         ui.separator();
 
-        ui.label(format!("Angle: {:.2}°", angle.mul(180./3.14159265)));
+        ui.label(format!("Angle: {:.2}°", angle.to_degrees()));
 
         ui.horizontal(|ui|
         {
@@ -211,8 +672,72 @@ pub(crate) fn paint_ui(
           ui.label(format!("v{}", VERSION.unwrap_or("unknown")));
           ui.separator();
           ui.label("Made by");
-          ui.hyperlink_to(format!("{}", AUTHORS.unwrap_or("unknown")), "https://github.com/an-Iceberg");
+          ui.hyperlink_to(AUTHORS.unwrap_or("unknown"), "https://github.com/an-Iceberg");
         });
-      });
+      }));
+    // The canvas ends where the panel starts, egui measures in points instead of pixels
+    *canvas_width = panel.response.rect.left() * egui_context.pixels_per_point();
+
+    paint_matrix_window(egui_context, graph, history, matrix_view, animation, all_paths);
   });
 }
+
+/// Shows the distances between all pairs of points, clicking on a distance shows its path
+fn paint_matrix_window(
+  egui_context: &Context,
+  graph: &mut DijkstraGraph,
+  history: &mut EditHistory,
+  matrix_view: &mut MatrixView,
+  animation: &mut Animation,
+  all_paths: &mut AllPaths,
+)
+{
+  Window::new("Distance matrix")
+    .open(&mut matrix_view.open)
+    .default_pos((10., 10.))
+    .default_size(Vec2::new(400., 300.))
+    .show(egui_context, |ui|
+    {
+      let Some(matrix) = &matrix_view.matrix else
+      {
+        ui.label(format!("The matrix is only shown for graphs with up to {} points.", MAX_MATRIX_POINTS));
+        return;
+      };
+
+      ui.label("Rows are the start, columns the end. Click on a distance to show its path.");
+      if matrix.has_negative_cycle()
+      { ui.colored_label(Color32::from_rgb(255, 64, 64), "There is a cycle of negative length, paths over it have no shortest length (-∞)."); }
+      ui.separator();
+
+      let ids: Vec<usize> = (0..graph.points().len()).filter(|id| graph.get(*id).is_some()).collect();
+
+      ScrollArea::both().show(ui, |ui|
+      {
+        Grid::new("distance_matrix")
+          .striped(true)
+          .show(ui, |ui|
+          {
+            ui.label("");
+            ids.iter().for_each(|to| { ui.strong(to.to_string()); });
+            ui.end_row();
+
+            for from in ids.iter()
+            {
+              ui.strong(from.to_string());
+              for to in ids.iter()
+              {
+                let selected = graph.start() == Some(*from) && graph.end() == Some(*to);
+                if ui.selectable_label(selected, describe_distance(matrix.distance(*from, *to))).clicked()
+                {
+                  animation.stop();
+                  history.set_start_and_end(graph, Some(*from), Some(*to));
+                  graph.find_shortest_path();
+                  all_paths.find(graph);
+                }
+              }
+              ui.end_row();
+            }
+          });
+      });
+    });
+}
@@ -1,38 +1,15 @@
-use crate::{graph::DijkstraGraph, PADDING, LINE_COLOR, POINT_COLOR, PATH_COLOR, BG_COLOR, LINE_LENGTH_COLOR};
+use rust_graph::{DijkstraGraph, EditHistory, LineKind, SpanningForest};
+use rust_graph::geometry::{quadratic_bezier, quadratic_bezier_exit, quadratic_bezier_tangent, self_loop_circle, self_loop_ends};
+use crate::{PADDING, LINE_COLOR, POINT_COLOR, PATH_COLOR, BG_COLOR, LINE_LENGTH_COLOR, CURRENT_COLOR, FRONTIER_COLOR, VISITED_COLOR, TREE_COLOR, NEGATIVE_CYCLE_COLOR};
+use crate::{BACK_LINE_COLOR, FORWARD_LINE_COLOR, CROSS_LINE_COLOR, CYCLE_COLOR, CRITICAL_PATH_COLOR, CUT_COLOR, HOVERED_LINE_COLOR, SELECTION_COLOR};
+use crate::{all_paths::AllPaths, animation::Animation, components::ComponentsView, flow::FlowView, selection::Selection, topological::TopologicalView, traversal::TraversalView};
 use macroquad::{
-  prelude::{
-    mouse_position, Color,
-  },
-  shapes::{draw_circle, draw_rectangle, draw_circle_lines, draw_line, draw_triangle, draw_hexagon}, text::{get_text_center, draw_text, measure_text}, math::Vec2, color::{YELLOW, MAGENTA, GREEN},
+  prelude::Color,
+  shapes::{draw_circle, draw_rectangle, draw_rectangle_lines, draw_circle_lines, draw_line, draw_triangle, draw_hexagon}, text::{get_text_center, draw_text, measure_text}, math::Vec2, color::{YELLOW, MAGENTA, GREEN},
 };
 use std::ops::{Div, Mul};
 use crate::Mode;
 
-pub(crate) fn is_point_in_circle(
-  point_x: f32, point_y: f32,
-  circle_x: f32, circle_y: f32,
-  circle_radius: f32
-) -> bool
-{
-  return (circle_x - point_x).powf(2_f32) + (circle_y - point_y).powf(2_f32) <= circle_radius.powf(2_f32);
-}
-
-pub(crate) fn is_point_in_rectangle(
-  point_x: f32, point_y: f32,
-  rectangle_x: f32, rectangle_y: f32,
-  rectangle_width: f32,
-  rectangle_height: f32,
-) -> bool
-{
-  if point_x < rectangle_x
-    || point_y < rectangle_y
-    || point_x > (rectangle_x + rectangle_width)
-    || point_y > (rectangle_y + rectangle_height)
-  { return false; }
-
-  return true;
-}
-
 pub(crate) fn draw_pill(x: f32, y: f32, width: f32, height: f32, color: Color)
 {
   draw_rectangle(x, y, width, height, color);
@@ -45,14 +22,19 @@ pub(crate) fn handle_mouse_input(
   left_mouse_down: bool,
   left_mouse_released: bool,
   right_mouse_pressed: bool,
+  shift_down: bool,
+  mouse: (f32, f32),
   mode: &Mode,
   graph: &mut DijkstraGraph,
+  history: &mut EditHistory,
   hovered_point_id_option: &Option<usize>,
   selected_point_id_option: &mut Option<usize>,
-  line_length: &mut u16
+  selection: &mut Selection,
+  hovered_line: &Option<(usize, usize)>,
+  line_length: &mut i32
 )
 {
-  use Mode::{Move, Line, Point, Path};
+  use Mode::{Move, Line, Point, Path, Traverse, Flow};
 
   match (
     mode,
@@ -66,33 +48,35 @@ pub(crate) fn handle_mouse_input(
   {
     // --- MOVE ---
 
-    // Select a point to be moved around
+    // Add a point to the selection or take it out again
+    (Move, true, _, _, false, Some(hovered_point_id), _) if shift_down =>
+      selection.toggle(*hovered_point_id),
+
+    // Grab the selection to move it around
     (Move, true, _, _, false, Some(hovered_point_id), _) =>
-      *selected_point_id_option = Some(*hovered_point_id),
+      selection.grab(graph, history, *hovered_point_id, mouse),
 
-    // Move a point around
-    (Move, _, true, _, false, _, Some(selected_point_id)) =>
-    {
-      if let Some(point) = graph.get_mut(*selected_point_id)
-      {
-        point.x = mouse_position().0;
-        point.y = mouse_position().1;
-      }
-    },
+    // Start a rubber band to select all points inside of it
+    (Move, true, _, _, false, None, _) =>
+      selection.start_rubber_band(mouse, shift_down),
+
+    // Move the selection around or drag the rubber band open
+    (Move, _, true, _, false, _, _) =>
+      selection.drag(graph, mouse),
 
-    // Releasing the selected point
+    // Releasing the selection or the rubber band
     (Move, _, _, true, false, _, _) =>
-      *selected_point_id_option = None,
+      selection.release(graph, history),
 
     // --- POINT ---
 
     // Create a point
     (Point, true, _, _, false, None, None) =>
-      graph.append_point(mouse_position().0, mouse_position().1),
+      history.append_point(graph, mouse.0, mouse.1),
 
     // Remove a point
     (Point, false, _, _, true, Some(hovered_point_id), _) =>
-      graph.remove_point(*hovered_point_id),
+      history.remove_point(graph, *hovered_point_id),
 
     // --- LINE ---
 
@@ -107,44 +91,51 @@ pub(crate) fn handle_mouse_input(
     // Select a point to draw the line to
     (Line, true, _, _, false, Some(hovered_point_id), Some(selected_point_id)) =>
     {
-      graph.add_line(*selected_point_id, *hovered_point_id, *line_length);
+      history.add_line(graph, *selected_point_id, *hovered_point_id, *line_length);
       *selected_point_id_option = None;
     },
 
     // Deletes the selected line
     (Line, false, _, _, true, Some(hovered_point_id), Some(selected_point_id)) =>
     {
-      graph.remove_line(*selected_point_id, *hovered_point_id);
+      history.remove_line(graph, *selected_point_id, *hovered_point_id);
       *selected_point_id_option = None;
     },
 
+    // Deletes the line under the mouse
+    (Line, false, _, _, true, None, None) =>
+    {
+      if let Some((from, to)) = hovered_line
+      { history.remove_line(graph, *from, *to); }
+    },
+
     // --- PATH ---
 
     // Select a start point with left click
-    (Path, true, _, _, false, Some(hovered_point_id), None) =>
+    (Path | Traverse | Flow, true, _, _, false, Some(hovered_point_id), None) =>
     {
-      graph.set_start(*hovered_point_id);
+      history.set_start(graph, Some(*hovered_point_id));
       graph.clear_path();
     },
 
     // Unsetting the start point
-    (Path, true, _, _, false, None, None) =>
+    (Path | Traverse | Flow, true, _, _, false, None, None) =>
     {
-      graph.clear_start();
+      history.set_start(graph, None);
       graph.clear_path();
     },
 
     // Select an end point with right click
-    (Path, false, _, _, true, Some(hovered_point_id), None) =>
+    (Path | Flow, false, _, _, true, Some(hovered_point_id), None) =>
     {
-      graph.set_end(*hovered_point_id);
+      history.set_end(graph, Some(*hovered_point_id));
       graph.clear_path();
     },
 
     // Unsetting the end point
-    (Path, false, _, _, true, None, None) =>
+    (Path | Flow, false, _, _, true, None, None) =>
     {
-      graph.clear_end();
+      history.set_end(graph, None);
       graph.clear_path();
     },
 
@@ -161,30 +152,59 @@ pub(crate) fn paint_graph(
   arrow_head_length: &f32,
   hovered_point_id: &mut Option<usize>,
   selected_point_id: &Option<usize>,
+  selection: Option<&Selection>,
+  hovered_line: &Option<(usize, usize)>,
   hexagons: &bool,
+  animation: &Animation,
+  all_paths: &AllPaths,
+  spanning_forest: Option<&SpanningForest>,
+  traversal_view: Option<&TraversalView>,
+  components_view: Option<&ComponentsView>,
+  topological_view: Option<&TopologicalView>,
+  flow_view: Option<&FlowView>,
 )
 {
   // Paint lines
   paint_lines(graph, path_thickness, base_point, radius);
-  paint_path(graph, path_thickness);
+  if let Some(forest) = spanning_forest
+  { paint_spanning_tree(graph, forest, path_thickness, radius); }
+  if let Some(traversal_view) = traversal_view
+  { paint_traversal(graph, traversal_view, path_thickness, radius); }
+  else if let Some(topological_view) = topological_view
+  { paint_topological(graph, topological_view, path_thickness, radius); }
+  else if let Some(flow_view) = flow_view
+  { paint_flow(graph, flow_view, path_thickness, radius); }
+  // The ids of the path don't match the points of the condensation
+  else if components_view.is_none()
+  { paint_path(graph, path_thickness, all_paths, radius); }
+  paint_negative_cycle(graph, path_thickness, radius);
+  paint_current_line(graph, animation, path_thickness, radius);
+  if let Some(line) = hovered_line
+  { paint_highlighted_lines(graph, &[*line], radius, *path_thickness + 1.5, HOVERED_LINE_COLOR); }
   paint_arrow_heads(graph, radius, angle, arrow_head_length, base_point);
-  paint_line_lengths(graph);
+  paint_line_lengths(graph, radius, flow_view.map(|flow_view| flow_view.labels.as_slice()));
 
   // Paint points
-  paint_points(graph, radius, hovered_point_id, selected_point_id, hexagons);
+  paint_points(graph, radius, hovered_point_id, selected_point_id, selection, hexagons, animation, components_view);
+  if animation.search.is_some()
+  { paint_distances(graph, radius); }
+  if let Some(traversal_view) = traversal_view
+  { paint_pills_below(graph, &traversal_view.labels(), radius); }
+  if let Some(topological_view) = topological_view
+  { paint_pills_below(graph, &topological_view.labels(), radius); }
 
   // Paints start label
   if let Some(start_id) = graph.start()
   {
     if let Some(start_point) = graph.get(start_id)
-    { paint_label("Start", start_point.x, start_point.y, &radius); }
+    { paint_label("Start", start_point.x, start_point.y, radius); }
   }
 
   // Paints end label
   if let Some(end_id) = graph.end()
   {
     if let Some(end_point) = graph.get(end_id)
-    { paint_label("End", end_point.x, end_point.y, &radius); }
+    { paint_label("End", end_point.x, end_point.y, radius); }
   }
 }
 
@@ -212,23 +232,159 @@ fn paint_label(text: &str, x: f32, y: f32, radius: &f32)
   );
 }
 
-fn paint_path(graph: &DijkstraGraph, path_thiccness: &f32)
+/// Draws the given lines thicker and in another colour over the regular ones, from the center of one point to the
+/// center of the other. Curved lines are followed along their curve, self-loops around their circle.
+fn paint_highlighted_lines(graph: &DijkstraGraph, lines: &[(usize, usize)], radius: &f32, thickness: f32, color: u32)
 {
-  let Some(path) = graph.get_path() else { return; };
+  lines.iter()
+    .filter(|(from_id, to_id)| from_id == to_id)
+    .filter_map(|(id, _)| graph.get(*id))
+    .for_each(|point|
+    {
+      let ((center_x, center_y), loop_radius) = self_loop_circle(point.x, point.y, *radius);
+      draw_circle_lines(center_x, center_y, loop_radius, thickness, Color::from_hex(color));
+    });
 
-  path.iter().zip(path.iter().skip(1))
-    .map(|(from_id, to_id)| (graph.get(*from_id), graph.get(*to_id)))
-    .filter(|(from_option, to_option)| from_option.is_some() && to_option.is_some())
-    .map(|(from_option, to_option)| (from_option.as_ref().unwrap(), to_option.as_ref().unwrap()))
-    .for_each(|(from, to)|
+  lines.iter()
+    .filter_map(|(from_id, to_id)| Some((graph.line_curve(*from_id, *to_id)?, graph.is_curved(*from_id, *to_id))))
+    .for_each(|((start, control, end), is_curved)|
     {
-      draw_line(
-        from.x,
-        from.y,
-        to.x,
-        to.y,
-        *path_thiccness + 1.5,
-        Color::from_hex(PATH_COLOR),
+      if is_curved
+      { paint_curve(start, control, end, 1., thickness, Color::from_hex(color)); }
+      else
+      { draw_line(start.0, start.1, end.0, end.1, thickness, Color::from_hex(color)); }
+    });
+}
+
+fn paint_path(graph: &DijkstraGraph, path_thiccness: &f32, all_paths: &AllPaths, radius: &f32)
+{
+  let lines: Vec<(usize, usize)> = if all_paths.show_all && !all_paths.lines.is_empty()
+  { all_paths.lines.clone() }
+  else
+  {
+    // The path selected among all shortest paths takes precedence over the one the search has found
+    let Some(path) = all_paths.selected().cloned().or_else(|| graph.get_path()) else { return; };
+    path.iter().copied().zip(path.iter().copied().skip(1)).collect()
+  };
+
+  paint_highlighted_lines(graph, &lines, radius, *path_thiccness + 1.5, PATH_COLOR);
+}
+
+/// Highlights the lines of the minimum spanning forest, they have no direction so they span the whole way between
+/// the two points
+fn paint_spanning_tree(graph: &DijkstraGraph, forest: &SpanningForest, path_thickness: &f32, radius: &f32)
+{
+  let lines: Vec<(usize, usize)> = forest.lines.iter().map(|(a, b, _)| (*a, *b)).collect();
+  paint_highlighted_lines(graph, &lines, radius, *path_thickness + 1.5, TREE_COLOR);
+}
+
+/// Highlights the traversal tree like a path, and every other line the traversal has looked at in the colour of its
+/// kind
+fn paint_traversal(graph: &DijkstraGraph, traversal_view: &TraversalView, path_thickness: &f32, radius: &f32)
+{
+  let Some(traversal) = &traversal_view.traversal else { return; };
+
+  for (kind, thickness, color) in [
+    (LineKind::Tree, *path_thickness + 1.5, PATH_COLOR),
+    (LineKind::Back, *path_thickness, BACK_LINE_COLOR),
+    (LineKind::Forward, *path_thickness, FORWARD_LINE_COLOR),
+    (LineKind::Cross, *path_thickness, CROSS_LINE_COLOR),
+  ]
+  {
+    let lines: Vec<(usize, usize)> = traversal.lines.iter()
+      .filter(|(_, _, line_kind)| *line_kind == kind)
+      .map(|(from, to, _)| (*from, *to))
+      .collect();
+    paint_highlighted_lines(graph, &lines, radius, thickness, color);
+  }
+}
+
+/// Highlights the critical path, or the cycle that prevents a topological order
+fn paint_topological(graph: &DijkstraGraph, topological_view: &TopologicalView, path_thickness: &f32, radius: &f32)
+{
+  if let Some(cycle) = &topological_view.cycle
+  {
+    let lines: Vec<(usize, usize)> = cycle.iter().copied().zip(cycle.iter().copied().cycle().skip(1)).collect();
+    paint_highlighted_lines(graph, &lines, radius, *path_thickness + 1.5, CYCLE_COLOR);
+  }
+
+  if let Some(critical_path) = &topological_view.critical_path
+  {
+    let path = &critical_path.path;
+    let lines: Vec<(usize, usize)> = path.iter().copied().zip(path.iter().copied().skip(1)).collect();
+    paint_highlighted_lines(graph, &lines, radius, *path_thickness + 1.5, CRITICAL_PATH_COLOR);
+  }
+}
+
+/// Highlights the lines that carry flow and either the lines of the minimum cut or the last augmenting path
+fn paint_flow(graph: &DijkstraGraph, flow_view: &FlowView, path_thickness: &f32, radius: &f32)
+{
+  let Some(flow) = &flow_view.flow else { return; };
+
+  paint_highlighted_lines(graph, &flow_view.used_lines(), radius, *path_thickness + 1.5, PATH_COLOR);
+  if flow_view.step.is_none()
+  { paint_highlighted_lines(graph, &flow.min_cut(), radius, *path_thickness + 1.5, CUT_COLOR); }
+  if let Some(augmenting_path) = flow_view.current_path()
+  {
+    let path = &augmenting_path.path;
+    let lines: Vec<(usize, usize)> = path.iter().copied().zip(path.iter().copied().skip(1)).collect();
+    paint_highlighted_lines(graph, &lines, radius, *path_thickness, CURRENT_COLOR);
+  }
+}
+
+/// Highlights the cycle of negative length Bellman-Ford has found, it takes the place of the path
+fn paint_negative_cycle(graph: &DijkstraGraph, path_thickness: &f32, radius: &f32)
+{
+  let Some(cycle) = graph.negative_cycle() else { return; };
+
+  let lines: Vec<(usize, usize)> = cycle.iter().copied().zip(cycle.iter().copied().cycle().skip(1)).collect();
+  paint_highlighted_lines(graph, &lines, radius, *path_thickness + 1.5, NEGATIVE_CYCLE_COLOR);
+}
+
+/// Highlights the line the animation has looked at during its last step
+fn paint_current_line(graph: &DijkstraGraph, animation: &Animation, path_thickness: &f32, radius: &f32)
+{
+  let Some(line) = animation.current_line() else { return; };
+  paint_highlighted_lines(graph, &[line], radius, *path_thickness + 1.5, CURRENT_COLOR);
+}
+
+/// Paints the tentative distance of every reached point below it
+fn paint_distances(graph: &DijkstraGraph, radius: &f32)
+{
+  let distances: Vec<(usize, String)> = graph.points()
+    .iter()
+    .enumerate()
+    .filter_map(|(id, point_option)| Some((id, point_option.as_ref()?.distance()?.to_string())))
+    .collect();
+
+  paint_pills_below(graph, &distances, radius);
+}
+
+/// Paints a pill with the given text below each of the given points
+fn paint_pills_below(graph: &DijkstraGraph, texts: &[(usize, String)], radius: &f32)
+{
+  texts.iter()
+    .filter_map(|(id, text)| Some((graph.get(*id)?, text)))
+    .for_each(|(point, text)|
+    {
+      let text_center = get_text_center(text.as_str(), None, 20, 1.0, 0.0);
+      let text_dimensions = measure_text(text.as_str(), None, 20, 1.0);
+
+      // The same 2 pixel gap as for the start and end labels
+      draw_pill(
+        point.x - text_dimensions.width.div(2.0),
+        point.y + radius + 2.0,
+        text_dimensions.width,
+        text_dimensions.height + PADDING.mul(2) as f32,
+        Color::from_hex(LINE_LENGTH_COLOR),
+      );
+
+      draw_text(
+        text.as_str(),
+        point.x - text_center.x,
+        point.y - text_center.y + radius + text_dimensions.height.div(2.0) + PADDING as f32 + 2.0,
+        20.0,
+        Color::from_hex(BG_COLOR)
       );
     });
 }
@@ -238,9 +394,29 @@ fn paint_points(
   radius: &f32,
   hovered_point_id_option: &mut Option<usize>,
   selected_point_id_option: &Option<usize>,
-  hexagons: &bool
+  selection: Option<&Selection>,
+  hexagons: &bool,
+  animation: &Animation,
+  components_view: Option<&ComponentsView>,
 )
 {
+  let frontier = match &animation.search
+  {
+    Some(search) => search.frontier(graph),
+    None => vec![],
+  };
+
+  // While the algorithm is animated the points are coloured by their state, in components mode by their component
+  let point_color = |id: usize, visited: bool|
+  {
+    if let Some(color) = components_view.and_then(|components_view| components_view.color(id)) { return color; }
+    if animation.search.is_none() { return Color::from_hex(POINT_COLOR); }
+    if animation.current() == Some(id) { return Color::from_hex(CURRENT_COLOR); }
+    if frontier.binary_search(&id).is_ok() { return Color::from_hex(FRONTIER_COLOR); }
+    if visited { return Color::from_hex(VISITED_COLOR); }
+    return Color::from_hex(POINT_COLOR);
+  };
+
   graph.points()
     .iter()
     .enumerate()
@@ -250,9 +426,9 @@ fn paint_points(
     {
       // Drawing the points
       if *hexagons
-      { draw_hexagon(point.x, point.y, *radius, 0., true, Color::from_hex(0xffffff), Color::from_hex(POINT_COLOR)); }
+      { draw_hexagon(point.x, point.y, *radius, 0., true, Color::from_hex(0xffffff), point_color(id, point.visited())); }
       else
-      { draw_circle(point.x, point.y, *radius, if Some(id) == *selected_point_id_option { YELLOW } else { Color::from_hex(POINT_COLOR) }); }
+      { draw_circle(point.x, point.y, *radius, if Some(id) == *selected_point_id_option { YELLOW } else { point_color(id, point.visited()) }); }
 
       let text_center = get_text_center(id.to_string().as_str(), None, 20, 1.0, 0.0);
 
@@ -279,6 +455,22 @@ fn paint_points(
     }
   }
 
+  // Drawing a thicker outline around the selected points and the rubber band that selects more of them
+  if let Some(selection) = selection
+  {
+    selection.ids.iter()
+      .filter_map(|id| graph.get(*id))
+      .for_each(|point| draw_circle_lines(point.x, point.y, *radius + 4_f32, 2.5, Color::from_hex(SELECTION_COLOR)));
+
+    if let Some(((x1, y1), (x2, y2))) = selection.rubber_band
+    {
+      let (x, y, width, height) = (x1.min(x2), y1.min(y2), (x1 - x2).abs(), (y1 - y2).abs());
+      let color = Color::from_hex(SELECTION_COLOR);
+      draw_rectangle(x, y, width, height, Color { a: 0.15, ..color });
+      draw_rectangle_lines(x, y, width, height, 1.5, color);
+    }
+  }
+
   // Reset the hovered point id
   *hovered_point_id_option = None;
 }
@@ -291,31 +483,50 @@ fn paint_arrow_heads(
   base_point: &f32,
 )
 {
+  // Undirected lines lead both ways
+  if graph.is_undirected() { return; }
+
   graph.lines()
     .iter()
-    .for_each(|(_, from, _, _, to)|
+    .for_each(|(from_id, from, _, to_id, to)|
     {
-      let mut direction = Vec2
+      // The tip of the triangle that touches the node and the direction from there back along the line
+      let (arrow_head_location, direction) = if from_id == to_id
       {
-        x: from.x - to.x,
-        y: from.y - to.y
-      };
-      let direction_length = direction.length();
-      direction = direction.normalize();
-
-      // Calculating the tip of the triangle that touches the node (position + (direction * (radius / length)))
-      let arrow_head_location = Vec2
+        // Self-loops run clockwise, the arrow head follows the tangent of their circle where they enter the node
+        let (center, _) = self_loop_circle(to.x, to.y, *radius);
+        let (_, entering) = self_loop_ends(to.x, to.y, *radius);
+        let outwards = Vec2::from(entering) - Vec2::from(center);
+        (Vec2::from(entering), Vec2 { x: outwards.y, y: -outwards.x }.normalize())
+      }
+      else if graph.is_curved(*from_id, *to_id)
+      {
+        // Curved lines meet the node at an angle, the arrow head follows the tangent of the curve there
+        let (start, control, end) = graph.line_curve(*from_id, *to_id).unwrap();
+        let t = quadratic_bezier_exit(start, control, end, *radius);
+        let tangent = Vec2::from(quadratic_bezier_tangent(start, control, end, t));
+        (Vec2::from(quadratic_bezier(start, control, end, t)), -tangent.normalize())
+      }
+      else
       {
-        x: to.x + (direction.x * (radius/* + 2.*/)),
-        y: to.y + (direction.y * (radius/* + 2.*/)),
+        let direction = Vec2
+        {
+          x: from.x - to.x,
+          y: from.y - to.y
+        }.normalize();
+
+        // Calculating the tip of the triangle that touches the node (position + (direction * radius))
+        (Vec2 { x: to.x + (direction.x * radius), y: to.y + (direction.y * radius) }, direction)
       };
 
+      // Large arrow heads would cover the whole self-loop
+      let (arrow_head_length, base_point) = if from_id == to_id
+      { (arrow_head_length.min(*radius), base_point.min(radius.div(2.))) }
+      else
+      { (*arrow_head_length, *base_point) };
+
       // This point is at the base of the arrow head that "connects" it to the line
-      let helper_point = Vec2
-      {
-        x: to.x + (direction.x * (radius + base_point)),
-        y: to.y + (direction.y * (radius + base_point)),
-      };
+      let helper_point = arrow_head_location + direction.mul(base_point);
 
       /*
       draw_line(
@@ -334,6 +545,8 @@ fn paint_arrow_heads(
         L2 is the length of the arrow head
         a is the angle
 
+        With the direction (x1 - x2) / L1 and (y1 - y2) / L1 already normalised, L1 drops out of the formula
+
         Formula:
         x3 = x2 + L2/L1 * [(x1 - x2) * cos(a) + (y1 - y2) * sin(a)]
         y3 = y2 + L2/L1 * [(y1 - y2) * cos(a) - (x1 - x2) * sin(a)]
@@ -349,8 +562,8 @@ fn paint_arrow_heads(
         helper_point,
         Vec2
         {
-          x: arrow_head_location.x + ((arrow_head_length / direction_length) * (((from.x - to.x) * angle.cos()) - ((from.y - to.y) * angle.sin()))),
-          y: arrow_head_location.y + ((arrow_head_length / direction_length) * (((from.y - to.y) * angle.cos()) + ((from.x - to.x) * angle.sin()))),
+          x: arrow_head_location.x + (arrow_head_length * ((direction.x * angle.cos()) - (direction.y * angle.sin()))),
+          y: arrow_head_location.y + (arrow_head_length * ((direction.y * angle.cos()) + (direction.x * angle.sin()))),
         },
         Color::from_hex(LINE_COLOR)
       );
@@ -361,8 +574,8 @@ fn paint_arrow_heads(
         helper_point,
         Vec2
         {
-          x: arrow_head_location.x + ((arrow_head_length / direction_length) * (((from.x - to.x) * angle.cos()) + ((from.y - to.y) * angle.sin()))),
-          y: arrow_head_location.y + ((arrow_head_length / direction_length) * (((from.y - to.y) * angle.cos()) - ((from.x - to.x) * angle.sin()))),
+          x: arrow_head_location.x + (arrow_head_length * ((direction.x * angle.cos()) + (direction.y * angle.sin()))),
+          y: arrow_head_location.y + (arrow_head_length * ((direction.y * angle.cos()) - (direction.x * angle.sin()))),
         },
         Color::from_hex(LINE_COLOR)
       );
@@ -373,15 +586,35 @@ fn paint_lines(graph: &DijkstraGraph, path_thickness: &f32, base_point: &f32, ra
 {
   graph.lines()
     .iter()
-    .for_each(|(_, from, _, _, to)|
+    // Both directions of an undirected line are painted as one
+    .filter(|(from_id, _, _, to_id, _)| !graph.is_undirected() || from_id <= to_id)
+    .for_each(|(from_id, from, _, to_id, to)|
     {
+      // The part of the circle inside the point is painted over by it
+      if from_id == to_id
+      {
+        let ((center_x, center_y), loop_radius) = self_loop_circle(from.x, from.y, *radius);
+        draw_circle_lines(center_x, center_y, loop_radius, *path_thickness, Color::from_hex(LINE_COLOR));
+        return;
+      }
+
+      // Curves end where the base of the arrow head is
+      if graph.is_curved(*from_id, *to_id)
+      {
+        let (start, control, end) = graph.line_curve(*from_id, *to_id).unwrap();
+        let t = quadratic_bezier_exit(start, control, end, *radius + *base_point);
+        paint_curve(start, control, end, t, *path_thickness, Color::from_hex(LINE_COLOR));
+        return;
+      }
+
       let mut back_direction = Vec2
       {
         x: to.x - from.x,
         y: to.y - from.y
       };
       back_direction = back_direction.normalize();
-      back_direction = back_direction.mul(*radius + *base_point);
+      // Without an arrow head the line goes all the way to the center of the point
+      back_direction = if graph.is_undirected() { Vec2::ZERO } else { back_direction.mul(*radius + *base_point) };
 
       draw_line(
         from.x,
@@ -394,20 +627,48 @@ fn paint_lines(graph: &DijkstraGraph, path_thickness: &f32, base_point: &f32, ra
     });
 }
 
-fn paint_line_lengths(graph: &DijkstraGraph)
+/// Paints the quadratic Bézier curve from its start up to `t_end` as straight segments
+fn paint_curve(start: (f32, f32), control: (f32, f32), end: (f32, f32), t_end: f32, thickness: f32, color: Color)
 {
+  const SEGMENTS: usize = 24;
+
+  (0..SEGMENTS).for_each(|segment|
+  {
+    let from = quadratic_bezier(start, control, end, t_end * segment as f32 / SEGMENTS as f32);
+    let to = quadratic_bezier(start, control, end, t_end * (segment + 1) as f32 / SEGMENTS as f32);
+    draw_line(from.0, from.1, to.0, to.1, thickness, color);
+    // Fills the gaps between the segments
+    draw_circle(to.0, to.1, thickness / 2., color);
+  });
+}
+
+/// Paints the length of every line onto it, or the given labels in the order of the lines if there are any
+fn paint_line_lengths(graph: &DijkstraGraph, radius: &f32, labels: Option<&[String]>)
+{
+  // Lines are labelled closer to the point they lead to, undirected ones only once in the middle
+  let ratio = if graph.is_undirected() { 0.5 } else { 2.0 / 3.0 };
+
   graph.lines()
     .iter()
-    .for_each(|(_, from, distance, _, to)|
+    .enumerate()
+    .filter(|(_, (from_id, _, _, to_id, _))| !graph.is_undirected() || from_id <= to_id)
+    .for_each(|(index, (from_id, from, distance, to_id, _))|
     {
-      let position = Vec2
+      let text = labels.and_then(|labels| labels.get(index).cloned()).unwrap_or_else(|| distance.to_string());
+
+      // Curved lines are labelled on their curve, so the labels of both directions don't overlap, self-loops on top
+      let position = match graph.line_curve(*from_id, *to_id)
       {
-        x: ((1.0 / 3.0) * from.x + (2.0 / 3.0) * to.x),
-        y: ((1.0 / 3.0) * from.y + (2.0 / 3.0) * to.y),
+        Some((start, control, end)) => Vec2::from(quadratic_bezier(start, control, end, ratio)),
+        None =>
+        {
+          let ((center_x, center_y), loop_radius) = self_loop_circle(from.x, from.y, *radius);
+          Vec2 { x: center_x, y: center_y - loop_radius }
+        },
       };
 
-      let text_center = get_text_center(distance.to_string().as_str(), None, 20, 1.0, 0.0);
-      let text_dimensions = measure_text(distance.to_string().as_str(), None, 20, 1.0);
+      let text_center = get_text_center(text.as_str(), None, 20, 1.0, 0.0);
+      let text_dimensions = measure_text(text.as_str(), None, 20, 1.0);
 
       draw_pill(
         position.x - text_dimensions.width.div(2.0),
@@ -418,7 +679,7 @@ fn paint_line_lengths(graph: &DijkstraGraph)
       );
 
       draw_text(
-        distance.to_string().as_str(),
+        text.as_str(),
         position.x - text_center.x,
         position.y - text_center.y,
         20.0,
@@ -426,8 +687,3 @@ fn paint_line_lengths(graph: &DijkstraGraph)
       );
     });
 }
-
-// Tests
-#[path = "./tests/utils_tests.rs"]
-#[cfg(test)]
-mod utils_tests;